@@ -0,0 +1,56 @@
+#[macro_use]
+extern crate criterion;
+extern crate soft_ascii_string;
+
+use criterion::{Criterion, BenchmarkId, Throughput};
+use soft_ascii_string::{SoftAsciiStr, SoftAsciiString};
+
+fn inputs() -> Vec<(&'static str, String)> {
+    let line = "From: Some One <some.one@example.com>\r\n";
+    vec![
+        ("short", "Content-Type".to_owned()),
+        ("long", line.repeat(1 << 16))
+    ]
+}
+
+fn case_mapping(c: &mut Criterion) {
+    let mut group = c.benchmark_group("case_mapping");
+    for (name, input) in inputs() {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("str::make_ascii_lowercase", name), &input, |b, input| {
+            let mut buf = input.clone();
+            b.iter(|| criterion::black_box(buf.as_mut_str()).make_ascii_lowercase())
+        });
+        group.bench_with_input(BenchmarkId::new("SoftAsciiStr::make_ascii_lowercase", name), &input, |b, input| {
+            let mut buf = SoftAsciiString::from_unchecked(input.as_str());
+            b.iter(|| criterion::black_box(&mut *buf).make_ascii_lowercase())
+        });
+        group.bench_with_input(BenchmarkId::new("str::to_ascii_uppercase", name), &input, |b, input| {
+            b.iter(|| criterion::black_box(input.as_str()).to_ascii_uppercase())
+        });
+        group.bench_with_input(BenchmarkId::new("SoftAsciiStr::to_ascii_uppercase", name), &input, |b, input| {
+            let soft = SoftAsciiStr::from_unchecked(input);
+            b.iter(|| criterion::black_box(soft).to_ascii_uppercase())
+        });
+    }
+    group.finish();
+}
+
+fn eq_ignore_ascii_case(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eq_ignore_ascii_case");
+    for (name, input) in inputs() {
+        let other = input.to_ascii_uppercase();
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("str::eq_ignore_ascii_case", name), &input, |b, input| {
+            b.iter(|| criterion::black_box(input.as_str()).eq_ignore_ascii_case(&other))
+        });
+        group.bench_with_input(BenchmarkId::new("SoftAsciiStr::eq_ignore_ascii_case", name), &input, |b, input| {
+            let soft = SoftAsciiStr::from_unchecked(input);
+            b.iter(|| criterion::black_box(soft).eq_ignore_ascii_case(&other))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, case_mapping, eq_ignore_ascii_case);
+criterion_main!(benches);
@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate criterion;
+extern crate soft_ascii_string;
+
+use criterion::{Criterion, BenchmarkId};
+use soft_ascii_string::{SoftAsciiChar, SoftAsciiStr, SoftAsciiString, SoftAsciiSmallString};
+
+fn inputs() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("header_name", "Content-Type"),
+        ("mime_type", "application/octet-stream"),
+        ("long", "multipart/alternative; boundary=\"--=_boundary\"")
+    ]
+}
+
+fn create(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create");
+    for (name, input) in inputs() {
+        let input = SoftAsciiStr::from_unchecked(input);
+        group.bench_with_input(BenchmarkId::new("SoftAsciiString", name), input, |b, input| {
+            b.iter(|| SoftAsciiString::from(criterion::black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("SoftAsciiSmallString", name), input, |b, input| {
+            b.iter(|| SoftAsciiSmallString::from(criterion::black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn push_chars(c: &mut Criterion) {
+    let mut group = c.benchmark_group("push_chars");
+    for (name, input) in inputs() {
+        let chars: Vec<SoftAsciiChar> = SoftAsciiStr::from_unchecked(input).chars().collect();
+        group.bench_with_input(BenchmarkId::new("SoftAsciiString", name), &chars, |b, chars| {
+            b.iter(|| {
+                let mut string = SoftAsciiString::new();
+                for &ch in criterion::black_box(chars) {
+                    string.push(ch);
+                }
+                string
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("SoftAsciiSmallString", name), &chars, |b, chars| {
+            b.iter(|| {
+                let mut string = SoftAsciiSmallString::new();
+                for &ch in criterion::black_box(chars) {
+                    string.push(ch);
+                }
+                string
+            })
+        });
+    }
+    group.finish();
+}
+
+fn clone_many(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone_100");
+    for (name, input) in inputs() {
+        let input = SoftAsciiStr::from_unchecked(input);
+        let strings: Vec<SoftAsciiString> = (0..100).map(|_| input.to_owned()).collect();
+        let small: Vec<SoftAsciiSmallString> = (0..100).map(|_| input.into()).collect();
+        group.bench_with_input(BenchmarkId::new("SoftAsciiString", name), &strings, |b, strings| {
+            b.iter(|| criterion::black_box(strings).clone())
+        });
+        group.bench_with_input(BenchmarkId::new("SoftAsciiSmallString", name), &small, |b, small| {
+            b.iter(|| criterion::black_box(small).clone())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, create, push_chars, clone_many);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate criterion;
+extern crate soft_ascii_string;
+
+use criterion::{Criterion, BenchmarkId, Throughput};
+use soft_ascii_string::{SoftAsciiStr, SoftAsciiString};
+
+fn inputs() -> Vec<(&'static str, String)> {
+    let line = "From: Some One <some.one@example.com>\r\n";
+    vec![
+        ("short", "hello@example.com".to_owned()),
+        ("long", line.repeat(1 << 16)),
+        ("long_non_ascii_at_end", line.repeat(1 << 16) + "ä")
+    ]
+}
+
+fn validation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validation");
+    for (name, input) in inputs() {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("str::is_ascii", name), &input, |b, input| {
+            b.iter(|| criterion::black_box(input.as_str()).is_ascii())
+        });
+        group.bench_with_input(BenchmarkId::new("SoftAsciiStr::from_str", name), &input, |b, input| {
+            b.iter(|| SoftAsciiStr::from_str(criterion::black_box(input.as_str())).is_ok())
+        });
+        group.bench_with_input(BenchmarkId::new("revalidate_soft_constraint", name), &input, |b, input| {
+            let soft = SoftAsciiStr::from_unchecked(input);
+            b.iter(|| criterion::black_box(soft).revalidate_soft_constraint().is_ok())
+        });
+        group.bench_with_input(BenchmarkId::new("SoftAsciiString::from_string", name), &input, |b, input| {
+            b.iter_batched(
+                || input.clone(),
+                |input| SoftAsciiString::from_string(input).is_ok(),
+                criterion::BatchSize::LargeInput
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, validation);
+criterion_main!(benches);
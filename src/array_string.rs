@@ -0,0 +1,270 @@
+use core::convert::TryFrom;
+use core::ops::{Deref, DerefMut};
+use core::str;
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+
+use error::CapacityError;
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+#[cfg(feature = "alloc")]
+use soft_string::SoftAsciiString;
+
+/// a `SoftAsciiString` like type storing up to `N` bytes inline
+///
+/// It does not allocate and is `Copy`, which makes it suited for short
+/// values like SMTP reply codes, tokens or header names. Appending more
+/// than `N` bytes fails with a `CapacityError`.
+///
+/// ```
+/// use soft_ascii_string::{SoftAsciiArrayString, SoftAsciiStr, SoftAsciiChar};
+///
+/// let mut code = SoftAsciiArrayString::<3>::new();
+/// code.try_push_str(SoftAsciiStr::from_str_const("25")).unwrap();
+/// code.try_push(SoftAsciiChar::from_char_const('0')).unwrap();
+/// assert_eq!(code, "250");
+/// assert!(code.try_push(SoftAsciiChar::from_char_const(' ')).is_err());
+/// ```
+#[derive(Copy, Clone)]
+pub struct SoftAsciiArrayString<const N: usize> {
+    // `buf[..len]` is always valid utf-8 as only whole chars/strs are appended
+    buf: [u8; N],
+    len: usize
+}
+
+impl<const N: usize> SoftAsciiArrayString<N> {
+
+    /// creates an empty `SoftAsciiArrayString`
+    #[inline]
+    pub const fn new() -> Self {
+        SoftAsciiArrayString { buf: [0; N], len: 0 }
+    }
+
+    /// returns the maximal number of bytes (`N`)
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// returns how many more bytes can be appended
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        N - self.len
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// appends `ch` or returns it in a `CapacityError` if it doesn't fit
+    pub fn try_push(&mut self, ch: SoftAsciiChar) -> Result<(), CapacityError<SoftAsciiChar>> {
+        let ch_len = char::from(ch).len_utf8();
+        if ch_len > self.remaining_capacity() {
+            return Err(CapacityError::new(ch));
+        }
+        char::from(ch).encode_utf8(&mut self.buf[self.len..]);
+        self.len += ch_len;
+        Ok(())
+    }
+
+    /// appends `s` or returns it in a `CapacityError` if it doesn't fit
+    ///
+    /// Nothing is appended if `s` doesn't fit completely.
+    pub fn try_push_str<'a>(&mut self, s: &'a SoftAsciiStr)
+        -> Result<(), CapacityError<&'a SoftAsciiStr>>
+    {
+        if s.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(s));
+        }
+        self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<SoftAsciiChar> {
+        let ch = self.as_str().chars().next_back()?;
+        self.len -= ch.len_utf8();
+        Some(SoftAsciiChar::rewrap(ch))
+    }
+
+    /// shortens the string to `new_len` bytes, does nothing if it's shorter
+    ///
+    /// # Panics
+    ///
+    /// if `new_len` is not on a char boundary
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len {
+            assert!(self.is_char_boundary(new_len), "new_len is not on a char boundary");
+            self.len = new_len;
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    #[inline]
+    pub fn as_soft_ascii_str(&self) -> &SoftAsciiStr {
+        // Safe: see the comment on `buf`
+        SoftAsciiStr::rewrap(unsafe { str::from_utf8_unchecked(&self.buf[..self.len]) })
+    }
+
+    #[inline]
+    pub fn as_mut_soft_ascii_str(&mut self) -> &mut SoftAsciiStr {
+        // Safe: see the comment on `buf`
+        SoftAsciiStr::rewrap_mut(unsafe { str::from_utf8_unchecked_mut(&mut self.buf[..self.len]) })
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.as_soft_ascii_str().as_str()
+    }
+}
+
+impl<const N: usize> Default for SoftAsciiArrayString<N> {
+    #[inline]
+    fn default() -> Self {
+        SoftAsciiArrayString::new()
+    }
+}
+
+impl<const N: usize> Deref for SoftAsciiArrayString<N> {
+    type Target = SoftAsciiStr;
+
+    #[inline]
+    fn deref(&self) -> &SoftAsciiStr {
+        self.as_soft_ascii_str()
+    }
+}
+
+impl<const N: usize> DerefMut for SoftAsciiArrayString<N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut SoftAsciiStr {
+        self.as_mut_soft_ascii_str()
+    }
+}
+
+impl_soft_ascii_str_traits!([const N: usize] SoftAsciiArrayString<N>);
+
+impl<'a, const N: usize> TryFrom<&'a SoftAsciiStr> for SoftAsciiArrayString<N> {
+    type Error = CapacityError<&'a SoftAsciiStr>;
+
+    fn try_from(s: &'a SoftAsciiStr) -> Result<Self, Self::Error> {
+        let mut array_string = SoftAsciiArrayString::new();
+        array_string.try_push_str(s)?;
+        Ok(array_string)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> TryFrom<SoftAsciiString> for SoftAsciiArrayString<N> {
+    type Error = CapacityError<SoftAsciiString>;
+
+    fn try_from(s: SoftAsciiString) -> Result<Self, Self::Error> {
+        let mut array_string = SoftAsciiArrayString::new();
+        match array_string.try_push_str(&s) {
+            Ok(()) => Ok(array_string),
+            Err(_) => Err(CapacityError::new(s))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> From<SoftAsciiArrayString<N>> for SoftAsciiString {
+    #[inline]
+    fn from(s: SoftAsciiArrayString<N>) -> SoftAsciiString {
+        s.as_soft_ascii_str().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::convert::TryFrom;
+    use ::{SoftAsciiChar, SoftAsciiStr};
+    use super::SoftAsciiArrayString;
+
+    #[test]
+    fn try_push_respects_capacity() {
+        let mut token = SoftAsciiArrayString::<4>::new();
+        assert_eq!(token.capacity(), 4);
+        assert_ok!(token.try_push(SoftAsciiChar::from_unchecked('a')));
+        assert_ok!(token.try_push_str(SoftAsciiStr::from_unchecked("bc")));
+        assert_eq!(token.remaining_capacity(), 1);
+        let err = assert_err!(token.try_push_str(SoftAsciiStr::from_unchecked("de")));
+        assert_eq!(*err.element(), "de");
+        assert_eq!(token, "abc");
+        assert_ok!(token.try_push(SoftAsciiChar::from_unchecked('d')));
+        assert!(token.is_full());
+        let err = assert_err!(token.try_push(SoftAsciiChar::from_unchecked('e')));
+        assert_eq!(err.into_element(), 'e');
+        assert_eq!(token, "abcd");
+    }
+
+    #[test]
+    fn pop_truncate_clear() {
+        let mut token = assert_ok!(
+            SoftAsciiArrayString::<8>::try_from(SoftAsciiStr::from_unchecked("hello")));
+        assert_eq!(token.pop(), Some(SoftAsciiChar::from_unchecked('o')));
+        token.truncate(2);
+        assert_eq!(token, "he");
+        token.truncate(10);
+        assert_eq!(token, "he");
+        token.clear();
+        assert!(token.is_empty());
+        assert_eq!(token.pop(), None);
+    }
+
+    #[test]
+    fn keeps_utf8_if_soft_constraint_is_broken() {
+        let mut token = SoftAsciiArrayString::<4>::new();
+        assert_ok!(token.try_push(SoftAsciiChar::from_unchecked('a')));
+        // `rewrap` isn't checked with `check-unchecked`
+        assert_err!(token.try_push_str(SoftAsciiStr::rewrap("↓↓")));
+        assert_ok!(token.try_push_str(SoftAsciiStr::rewrap("↓")));
+        assert_eq!(token, "a↓");
+        assert_eq!(token.pop(), Some(SoftAsciiChar::rewrap('↓')));
+        assert_eq!(token, "a");
+    }
+
+    #[test]
+    fn copy_eq_ord_hash_ignore_unused_capacity() {
+        let mut left = SoftAsciiArrayString::<8>::new();
+        assert_ok!(left.try_push_str(SoftAsciiStr::from_unchecked("abcd")));
+        let mut right = left;
+        left.truncate(2);
+        right.truncate(3);
+        right.pop();
+        assert_eq!(left, right);
+        assert!(left < assert_ok!(SoftAsciiArrayString::try_from(SoftAsciiStr::from_unchecked("abc"))));
+
+        let mut set = HashSet::new();
+        set.insert(left);
+        assert!(set.contains(&right));
+        assert!(set.contains(SoftAsciiStr::from_unchecked("ab")));
+    }
+
+    #[test]
+    fn display_and_deref() {
+        let mut code = assert_ok!(
+            SoftAsciiArrayString::<3>::try_from(SoftAsciiStr::from_unchecked("abc")));
+        assert_eq!(format!("{}", code), "abc");
+        assert_eq!(format!("{:?}", code), "\"abc\"");
+        code.make_ascii_uppercase();
+        assert_eq!(code.find('B'), Some(1));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn soft_ascii_string_conversions() {
+        use ::SoftAsciiString;
+        let string = SoftAsciiString::from_unchecked("250");
+        let code = assert_ok!(SoftAsciiArrayString::<3>::try_from(string));
+        assert_eq!(SoftAsciiString::from(code), "250");
+        let string = SoftAsciiString::from_unchecked("2500");
+        let err = assert_err!(SoftAsciiArrayString::<3>::try_from(string));
+        assert_eq!(err.into_element(), "2500");
+    }
+}
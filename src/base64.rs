@@ -0,0 +1,332 @@
+//! Base64 encoding and decoding ([RFC 4648]) including MIME line wrapping
+//!
+//! The encoding is selected through a [`Config`](struct.Config.html),
+//! the constants cover the commonly used variants.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::base64::{self, Config};
+//!
+//! let encoded = base64::encode(b"\xfb\xff", Config::STANDARD);
+//! assert_eq!(encoded, "+/8=");
+//! assert_eq!(base64::encode(b"\xfb\xff", Config::URL_SAFE_NO_PAD), "-_8");
+//!
+//! assert_eq!(base64::decode(&encoded, Config::STANDARD).unwrap(), b"\xfb\xff");
+//! ```
+//!
+//! [RFC 4648]: https://tools.ietf.org/html/rfc4648
+use core::fmt::{self, Display};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+const STANDARD_CHARS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_CHARS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// the alphabet used for encoding
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Alphabet {
+    /// uses `'+'` and `'/'` for 62 and 63
+    Standard,
+    /// uses `'-'` and `'_'` for 62 and 63
+    UrlSafe
+}
+
+impl Alphabet {
+    fn chars(self) -> &'static [u8; 64] {
+        match self {
+            Alphabet::Standard => STANDARD_CHARS,
+            Alphabet::UrlSafe => URL_SAFE_CHARS
+        }
+    }
+
+    fn decode(self, byte: u8) -> Option<u32> {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' if self == Alphabet::Standard => 62,
+            b'/' if self == Alphabet::Standard => 63,
+            b'-' if self == Alphabet::UrlSafe => 62,
+            b'_' if self == Alphabet::UrlSafe => 63,
+            _ => return None
+        };
+        Some(value as u32)
+    }
+}
+
+/// configuration of the base64 variant
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Config {
+    alphabet: Alphabet,
+    padding: bool,
+    line_len: Option<usize>
+}
+
+impl Config {
+
+    /// standard alphabet with padding
+    pub const STANDARD: Config = Config::new(Alphabet::Standard, true, None);
+
+    /// standard alphabet without padding
+    pub const STANDARD_NO_PAD: Config = Config::new(Alphabet::Standard, false, None);
+
+    /// url safe alphabet with padding
+    pub const URL_SAFE: Config = Config::new(Alphabet::UrlSafe, true, None);
+
+    /// url safe alphabet without padding
+    pub const URL_SAFE_NO_PAD: Config = Config::new(Alphabet::UrlSafe, false, None);
+
+    /// standard alphabet with padding and lines of 76 chars separated by CRLF ([RFC 2045])
+    ///
+    /// [RFC 2045]: https://tools.ietf.org/html/rfc2045#section-6.8
+    pub const MIME: Config = Config::new(Alphabet::Standard, true, Some(76));
+
+    /// creates a new config
+    ///
+    /// If `line_len` is `Some` the output is broken into lines of
+    /// (at most) the given length separated by `"\r\n"`, and line
+    /// breaks are skipped when decoding.
+    ///
+    /// # Panics
+    ///
+    /// if `line_len` is `Some(0)`
+    pub const fn new(alphabet: Alphabet, padding: bool, line_len: Option<usize>) -> Config {
+        if let Some(0) = line_len {
+            panic!("line length has to be at least 1");
+        }
+        Config { alphabet, padding, line_len }
+    }
+
+    /// returns the alphabet
+    pub fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
+    /// returns true if the output is padded with `'='`
+    pub fn padding(&self) -> bool {
+        self.padding
+    }
+
+    /// returns the line length if the output is line wrapped
+    pub fn line_len(&self) -> Option<usize> {
+        self.line_len
+    }
+}
+
+/// base64 encodes `input`
+pub fn encode(input: &[u8], config: Config) -> SoftAsciiString {
+    let mut out = SoftAsciiString::with_capacity(input.len().div_ceil(3) * 4);
+    encode_into(input, config, &mut out);
+    out
+}
+
+/// base64 encodes `input` appending the result to `out`
+pub fn encode_into(input: &[u8], config: Config, out: &mut SoftAsciiString) {
+    let chars = config.alphabet.chars();
+    let mut line_len = 0;
+    let mut push = |byte: u8| {
+        if config.line_len == Some(line_len) {
+            out.push_str(SoftAsciiStr::rewrap("\r\n"));
+            line_len = 0;
+        }
+        out.push(SoftAsciiChar::rewrap(byte as char));
+        line_len += 1;
+    };
+    for group in input.chunks(3) {
+        let mut buf = [0u8; 3];
+        buf[..group.len()].copy_from_slice(group);
+        let bits = ((buf[0] as u32) << 16) | ((buf[1] as u32) << 8) | buf[2] as u32;
+        for idx in 0..4 {
+            if idx <= group.len() {
+                push(chars[((bits >> (18 - 6 * idx)) & 0x3f) as usize]);
+            } else if config.padding {
+                push(b'=');
+            }
+        }
+    }
+}
+
+/// Error returned if base64 decoding failed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Base64Error {
+    /// the char at the given byte offset is not part of the alphabet
+    InvalidChar(usize),
+    /// the padding at the given byte offset is missing, misplaced or not allowed
+    InvalidPadding(usize),
+    /// the unused bits of the last char (at the given byte offset) are not zero
+    TrailingBits(usize),
+    /// the input ended in the middle of a byte
+    UnexpectedEnd
+}
+
+impl Display for Base64Error {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Base64Error::InvalidChar(idx) =>
+                write!(fter, "invalid base64 char at byte offset {}", idx),
+            Base64Error::InvalidPadding(idx) =>
+                write!(fter, "invalid base64 padding at byte offset {}", idx),
+            Base64Error::TrailingBits(idx) =>
+                write!(fter, "non zero trailing bits in base64 char at byte offset {}", idx),
+            Base64Error::UnexpectedEnd =>
+                write!(fter, "base64 input ended unexpectedly")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Base64Error {
+    fn description(&self) -> &str {
+        "base64 decoding failed"
+    }
+}
+
+/// decodes the base64 encoded `input`
+///
+/// If the config is padded the input has to be padded, too, if it is
+/// not padding is not allowed. If the config has a line length, CR
+/// and LF are ignored.
+pub fn decode(input: &SoftAsciiStr, config: Config) -> Result<Vec<u8>, Base64Error> {
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    decode_into(input, config, &mut out)?;
+    Ok(out)
+}
+
+/// decodes the base64 encoded `input` appending the result to `out`
+///
+/// If an error is returned `out` might have been partially written to.
+pub fn decode_into(input: &SoftAsciiStr, config: Config, out: &mut Vec<u8>)
+    -> Result<(), Base64Error>
+{
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    let mut data_len = 0usize;
+    let mut last_data_idx = 0;
+    let mut padding_start = None;
+    let mut padding_len = 0;
+    for (idx, byte) in input.as_bytes().iter().cloned().enumerate() {
+        if config.line_len.is_some() && (byte == b'\r' || byte == b'\n') {
+            continue;
+        }
+        if byte == b'=' {
+            if !config.padding || padding_len == 2 {
+                return Err(Base64Error::InvalidPadding(idx));
+            }
+            padding_start = padding_start.or(Some(idx));
+            padding_len += 1;
+            continue;
+        }
+        if let Some(padding_start) = padding_start {
+            return Err(Base64Error::InvalidPadding(padding_start));
+        }
+        let value = config.alphabet.decode(byte).ok_or(Base64Error::InvalidChar(idx))?;
+        bits = (bits << 6) | value;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+        data_len += 1;
+        last_data_idx = idx;
+    }
+
+    let remainder = data_len % 4;
+    if remainder == 1 {
+        return Err(Base64Error::UnexpectedEnd);
+    }
+    if config.padding && padding_len != (4 - remainder) % 4 {
+        return Err(Base64Error::InvalidPadding(padding_start.unwrap_or(input.len())));
+    }
+    if bits != 0 {
+        return Err(Base64Error::TrailingBits(last_data_idx));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use macros::soft;
+    use super::{encode, decode, Alphabet, Config, Base64Error};
+
+    /// test vectors from RFC 4648 section 10
+    const SAMPLES: &[(&str, &str)] = &[
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy")
+    ];
+
+    #[test]
+    fn rfc_samples() {
+        for &(decoded, encoded) in SAMPLES {
+            assert_eq!(encode(decoded.as_bytes(), Config::STANDARD), encoded);
+            assert_eq!(assert_ok!(decode(soft(encoded), Config::STANDARD)), decoded.as_bytes());
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(encode(decoded.as_bytes(), Config::STANDARD_NO_PAD), unpadded);
+            assert_eq!(
+                assert_ok!(decode(soft(unpadded), Config::STANDARD_NO_PAD)),
+                decoded.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn url_safe() {
+        assert_eq!(encode(b"\xfb\xff\xbf", Config::URL_SAFE), "-_-_");
+        assert_eq!(encode(b"\xfb\xff\xbf", Config::STANDARD), "+/+/");
+        assert_eq!(assert_ok!(decode(soft("-_-_"), Config::URL_SAFE_NO_PAD)), b"\xfb\xff\xbf");
+        assert_eq!(assert_err!(decode(soft("+/+/"), Config::URL_SAFE)), Base64Error::InvalidChar(0));
+    }
+
+    #[test]
+    fn mime_line_wrapping() {
+        let input = [0u8; 120];
+        let encoded = encode(&input, Config::MIME);
+        let lines: Vec<_> = encoded.as_str().split("\r\n").collect();
+        assert_eq!(lines.iter().map(|line| line.len()).collect::<Vec<_>>(), vec![76, 76, 8]);
+        assert_eq!(assert_ok!(decode(&encoded, Config::MIME)), &input[..]);
+    }
+
+    #[test]
+    fn custom_line_len() {
+        let config = Config::new(Alphabet::UrlSafe, false, Some(2));
+        assert_eq!(encode(b"foo", config), "Zm\r\n9v");
+        assert_eq!(encode(b"fo", config), "Zm\r\n8");
+    }
+
+    #[test]
+    fn padding_errors() {
+        assert_eq!(assert_err!(decode(soft("Zg="), Config::STANDARD)), Base64Error::InvalidPadding(2));
+        assert_eq!(assert_err!(decode(soft("Zg"), Config::STANDARD)), Base64Error::InvalidPadding(2));
+        assert_eq!(assert_err!(decode(soft("Zg=="), Config::STANDARD_NO_PAD)), Base64Error::InvalidPadding(2));
+        assert_eq!(assert_err!(decode(soft("Zg==Zg=="), Config::STANDARD)), Base64Error::InvalidPadding(2));
+        assert_eq!(assert_err!(decode(soft("Zm9v="), Config::STANDARD)), Base64Error::InvalidPadding(4));
+    }
+
+    #[test]
+    fn other_errors() {
+        assert_eq!(assert_err!(decode(soft("Zm9v\r\nZm9v"), Config::STANDARD)), Base64Error::InvalidChar(4));
+        assert_eq!(assert_err!(decode(soft("Zm9vZ"), Config::STANDARD_NO_PAD)), Base64Error::UnexpectedEnd);
+        assert_eq!(assert_err!(decode(soft("Zh=="), Config::STANDARD)), Base64Error::TrailingBits(1));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for &config in &[Config::STANDARD, Config::URL_SAFE_NO_PAD, Config::MIME] {
+            assert_eq!(assert_ok!(decode(&encode(&bytes, config), config)), bytes);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! byte level ascii case mapping and comparison
+//!
+//! Words of 8 bytes are mapped at once with bit operations, for comparing
+//! long inputs blocks of bytes are compared without branching (which the
+//! compiler can vectorize).
+//!
+//! Only the bytes `A`-`Z`/`a`-`z` are changed, all other bytes (including
+//! non us-ascii ones, if the soft constraint is broken) are kept as they
+//! are, so the result is valid utf-8 if the input is.
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+/// returns a mask with `0x20` set in each byte of `word` which is in `low..=high`
+#[inline(always)]
+fn case_bits(word: u64, low: u8, high: u8) -> u64 {
+    // using the low 7 bits, so that the additions can't carry into the next byte
+    let heptets = word & !HIGH_BITS;
+    let ge_low = heptets + (0x80 - low as u64) * ONES;
+    let gt_high = heptets + (0x80 - high as u64 - 1) * ONES;
+    // `!word` excludes non us-ascii bytes
+    ((ge_low & !gt_high & !word) & HIGH_BITS) >> 2
+}
+
+#[inline(always)]
+fn word_to_lowercase(word: u64) -> u64 {
+    word | case_bits(word, b'A', b'Z')
+}
+
+#[inline(always)]
+fn word_to_uppercase(word: u64) -> u64 {
+    word & !case_bits(word, b'a', b'z')
+}
+
+#[inline(always)]
+fn map_words<W, B>(bytes: &mut [u8], map_word: W, map_byte: B)
+    where W: Fn(u64) -> u64, B: FnMut(&mut u8)
+{
+    let mut chunks = bytes.chunks_exact_mut(8);
+    for chunk in chunks.by_ref() {
+        let word = map_word(read_word(chunk));
+        chunk.copy_from_slice(&word.to_ne_bytes());
+    }
+    chunks.into_remainder().iter_mut().for_each(map_byte);
+}
+
+/// maps `A`-`Z` to `a`-`z` in place
+pub(crate) fn make_lowercase(bytes: &mut [u8]) {
+    map_words(bytes, word_to_lowercase, u8::make_ascii_lowercase)
+}
+
+/// maps `a`-`z` to `A`-`Z` in place
+pub(crate) fn make_uppercase(bytes: &mut [u8]) {
+    map_words(bytes, word_to_uppercase, u8::make_ascii_uppercase)
+}
+
+/// compares `left` and `right` ignoring the case of `A`-`Z`/`a`-`z`
+pub(crate) fn eq_ignore_case(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    // no early return per byte, so that the loop over a block can be vectorized
+    let mut left_blocks = left.chunks_exact(BLOCK_SIZE);
+    let mut right_blocks = right.chunks_exact(BLOCK_SIZE);
+    for (left, right) in left_blocks.by_ref().zip(right_blocks.by_ref()) {
+        let mut diff = 0;
+        for (left, right) in left.iter().zip(right) {
+            diff |= left.to_ascii_lowercase() ^ right.to_ascii_lowercase();
+        }
+        if diff != 0 {
+            return false;
+        }
+    }
+    let mut left_words = left_blocks.remainder().chunks_exact(8);
+    let mut right_words = right_blocks.remainder().chunks_exact(8);
+    for (left, right) in left_words.by_ref().zip(right_words.by_ref()) {
+        if word_to_lowercase(read_word(left)) != word_to_lowercase(read_word(right)) {
+            return false;
+        }
+    }
+    left_words.remainder().eq_ignore_ascii_case(right_words.remainder())
+}
+
+const BLOCK_SIZE: usize = 32;
+
+#[inline(always)]
+fn read_word(bytes: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word.copy_from_slice(bytes);
+    u64::from_ne_bytes(word)
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+    use super::{make_lowercase, make_uppercase, eq_ignore_case};
+
+    const BASE: &[u8] = b"Hello World, hi THERE! [Some] @more `text` {}";
+
+    fn all_bytes_at_all_positions() -> Vec<Vec<u8>> {
+        let mut inputs = Vec::new();
+        for byte in 0..=255u8 {
+            for pos in 0..BASE.len() {
+                let mut input = BASE.to_vec();
+                input[pos] = byte;
+                inputs.push(input);
+            }
+        }
+        inputs
+    }
+
+    #[test]
+    fn same_as_std() {
+        for input in all_bytes_at_all_positions() {
+            let mut lower = input.clone();
+            make_lowercase(&mut lower);
+            assert_eq!(lower, input.to_ascii_lowercase());
+            let mut upper = input.clone();
+            make_uppercase(&mut upper);
+            assert_eq!(upper, input.to_ascii_uppercase());
+            assert!(eq_ignore_case(&lower, &upper));
+            assert!(eq_ignore_case(&input, &upper));
+            assert_eq!(eq_ignore_case(&input, BASE), input.eq_ignore_ascii_case(BASE));
+        }
+    }
+
+    #[test]
+    fn eq_ignore_case_differences() {
+        assert!(eq_ignore_case(b"", b""));
+        assert!(eq_ignore_case(b"Content-Type: text", b"content-type: TEXT"));
+        assert!(!eq_ignore_case(b"Content-Type: text", b"content-type: TEXT "));
+        assert!(!eq_ignore_case(b"Content-Type: text", b"content_type: text"));
+        assert!(!eq_ignore_case(b"Content-Type: tex1", b"content-type: text"));
+        // '@' / '`' and '[' / '{' differ by 0x20 but are not letters
+        assert!(!eq_ignore_case(b"@@@@@@@@[", b"````````{"));
+        assert!(!eq_ignore_case(b"\xc0\xc0\xc0\xc0\xc0\xc0\xc0\xc0", b"\xe0\xe0\xe0\xe0\xe0\xe0\xe0\xe0"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Enforcement of the soft constraint for unchecked constructors
+//!
+//! With the `check-unchecked` feature every `from_unchecked` (and
+//! `from_unchecked_mut`) of `SoftAsciiChar`, `SoftAsciiStr` and
+//! `SoftAsciiString` and the drop of the guards returned by
+//! `inner_str_mut_checked`/`inner_string_mut_checked` re-check that the value is
+//! us-ascii. What happens if it is not is decided by the process
+//! wide [`Policy`](enum.Policy.html), which defaults to `Policy::Panic`.
+//!
+//! `SoftAsciiStr::inner_str_mut` and `SoftAsciiString::inner_string_mut`
+//! return plain `&mut str`/`&mut String` references, changes made through
+//! them are _not_ checked. Use the `*_checked` variants to have them checked.
+//!
+//! Without the feature this module does not exist and the unchecked
+//! constructors don't do any checks.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::check::{self, Policy, UncheckedViolation};
+//!
+//! fn log_violation(violation: &UncheckedViolation) {
+//!     eprintln!("soft constraint violated: {}", violation);
+//! }
+//!
+//! check::set_policy(Policy::Hook(log_violation));
+//! ```
+use core::fmt::{self, Display};
+use core::mem;
+use core::panic::Location;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+use error::{FromSourceError, Violation};
+use validate;
+
+const POLICY_PANIC: usize = 0;
+const POLICY_DEBUG_ASSERT: usize = 1;
+const POLICY_HOOK: usize = 2;
+
+static POLICY: AtomicUsize = AtomicUsize::new(POLICY_PANIC);
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// what to do if an unchecked value is not us-ascii
+#[derive(Debug, Copy, Clone)]
+pub enum Policy {
+    /// panic (the default)
+    Panic,
+    /// fail a `debug_assert!`, i.e. panic in debug builds and ignore it otherwise
+    DebugAssert,
+    /// call the given function
+    Hook(fn(&UncheckedViolation))
+}
+
+/// sets the process wide policy
+pub fn set_policy(policy: Policy) {
+    let value = match policy {
+        Policy::Panic => POLICY_PANIC,
+        Policy::DebugAssert => POLICY_DEBUG_ASSERT,
+        Policy::Hook(hook) => {
+            HOOK.store(hook as *mut (), Ordering::SeqCst);
+            POLICY_HOOK
+        }
+    };
+    POLICY.store(value, Ordering::SeqCst);
+}
+
+/// returns the process wide policy
+pub fn policy() -> Policy {
+    match POLICY.load(Ordering::SeqCst) {
+        POLICY_DEBUG_ASSERT => Policy::DebugAssert,
+        POLICY_HOOK => Policy::Hook(load_hook()),
+        _ => Policy::Panic
+    }
+}
+
+fn load_hook() -> fn(&UncheckedViolation) {
+    let hook = HOOK.load(Ordering::SeqCst);
+    assert!(!hook.is_null(), "[BUG] hook policy without hook");
+    // Safe: HOOK is only ever set to a `fn(&UncheckedViolation)`
+    unsafe { mem::transmute::<*mut (), fn(&UncheckedViolation)>(hook) }
+}
+
+/// where a unchecked value came from
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// `SoftAsciiChar::from_unchecked`
+    SoftAsciiChar,
+    /// `SoftAsciiStr::from_unchecked`/`from_unchecked_mut`
+    SoftAsciiStr,
+    /// `SoftAsciiString::from_unchecked`
+    SoftAsciiString,
+    /// the guard returned by `SoftAsciiStr::inner_str_mut_checked`
+    InnerStrMut,
+    /// the guard returned by `SoftAsciiString::inner_string_mut_checked`
+    InnerStringMut
+}
+
+/// information about an unchecked value which is not us-ascii
+#[derive(Debug)]
+pub struct UncheckedViolation<'a> {
+    origin: Origin,
+    error: FromSourceError<&'a str>,
+    location: &'static Location<'static>
+}
+
+impl<'a> UncheckedViolation<'a> {
+
+    /// returns where the value came from
+    pub fn origin(&self) -> Origin {
+        self.origin
+    }
+
+    /// returns the error describing the violation, it's source is the unchecked value
+    pub fn error(&self) -> &FromSourceError<&'a str> {
+        &self.error
+    }
+
+    /// returns the location of the `from_unchecked`/`inner_*_mut_checked` call
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl<'a> Display for UncheckedViolation<'a> {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        write!(fter, "{:?} created at {} is not us-ascii, {}", self.origin, self.location, self.error)
+    }
+}
+
+/// checks `value` and applies the policy if it's not us-ascii
+#[track_caller]
+pub(crate) fn check(origin: Origin, value: &str) {
+    check_at(origin, value, Location::caller())
+}
+
+/// checks `value` when a guard is dropped
+///
+/// With `std` the check is skipped if the thread is already panicking, as
+/// a second panic (e.g. with `Policy::Panic`) would abort the process.
+pub(crate) fn check_on_drop(origin: Origin, value: &str, location: &'static Location<'static>) {
+    #[cfg(feature = "std")]
+    {
+        if ::std::thread::panicking() {
+            return;
+        }
+    }
+    check_at(origin, value, location)
+}
+
+/// like `check` but with an explicit location
+pub(crate) fn check_at(origin: Origin, value: &str, location: &'static Location<'static>) {
+    check_with(policy(), origin, value, location)
+}
+
+fn check_with(policy: Policy, origin: Origin, value: &str, location: &'static Location<'static>) {
+    let valid_up_to = match validate::first_non_ascii(value.as_bytes()) {
+        None => return,
+        Some(valid_up_to) => valid_up_to
+    };
+    let violation = UncheckedViolation {
+        origin, location,
+        error: FromSourceError::from_violation(value, Violation::at(value, valid_up_to))
+    };
+    match policy {
+        Policy::Panic => panic!("{}", violation),
+        Policy::DebugAssert => debug_assert!(false, "{}", violation),
+        Policy::Hook(hook) => hook(&violation)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::string::String;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use core::panic::Location;
+    use ::{SoftAsciiChar, SoftAsciiStr, SoftAsciiString};
+    use super::{check_with, Origin, Policy, UncheckedViolation};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_violation(violation: &UncheckedViolation) {
+        assert_eq!(violation.error().invalid_char(), Some('↓'));
+        assert_eq!(violation.origin(), Origin::SoftAsciiStr);
+        assert!(violation.location().file().ends_with("check.rs"));
+        CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn panic_message<F: FnOnce()>(func: F) -> String {
+        let payload = panic::catch_unwind(AssertUnwindSafe(func)).unwrap_err();
+        *payload.downcast::<String>().unwrap()
+    }
+
+    // the process wide policy is not changed as other tests run in parallel
+    #[test]
+    fn policies() {
+        let location = Location::caller();
+        let msg = panic_message(|| check_with(Policy::Panic, Origin::SoftAsciiStr, "a↓", location));
+        assert!(msg.starts_with("SoftAsciiStr created at "));
+        check_with(Policy::Panic, Origin::SoftAsciiStr, "abc", location);
+
+        let res = panic::catch_unwind(|| check_with(Policy::DebugAssert, Origin::SoftAsciiStr, "a↓", location));
+        assert_eq!(res.is_err(), cfg!(debug_assertions));
+
+        check_with(Policy::Hook(count_violation), Origin::SoftAsciiStr, "a↓", location);
+        check_with(Policy::Hook(count_violation), Origin::SoftAsciiStr, "abc", location);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn unchecked_values_are_checked() {
+        let msg = panic_message(|| { SoftAsciiChar::from_unchecked('↓'); });
+        assert!(msg.starts_with("SoftAsciiChar created at "));
+        assert!(msg.contains("check.rs"));
+        let msg = panic_message(|| { SoftAsciiStr::from_unchecked("a↓"); });
+        assert!(msg.starts_with("SoftAsciiStr created at "));
+        let mut buf = String::from("a↓");
+        let msg = panic_message(|| { SoftAsciiStr::from_unchecked_mut(&mut buf); });
+        assert!(msg.starts_with("SoftAsciiStr created at "));
+        let msg = panic_message(|| { SoftAsciiString::from_unchecked("a↓"); });
+        assert!(msg.starts_with("SoftAsciiString created at "));
+
+        assert_eq!(SoftAsciiStr::from_unchecked("abc"), "abc");
+        let mut string = SoftAsciiString::from_unchecked("ab");
+        string.inner_string_mut_checked().push('c');
+        string.inner_str_mut_checked().make_ascii_uppercase();
+        assert_eq!(string, "ABC");
+
+        let msg = panic_message(|| string.inner_string_mut_checked().push('↓'));
+        assert!(msg.starts_with("InnerStringMut created at "));
+        assert!(msg.contains("check.rs"));
+        // the unchecked accessors don't check
+        string.inner_string_mut().push('↓');
+        let msg = panic_message(|| string.inner_str_mut_checked().make_ascii_lowercase());
+        assert!(msg.starts_with("InnerStrMut created at "));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn guards_are_not_checked_while_panicking() {
+        let mut string = SoftAsciiString::from_unchecked("ab");
+        let msg = panic_message(|| {
+            let mut guard = string.inner_string_mut_checked();
+            guard.push('↓');
+            panic!("{}", "first panic");
+        });
+        assert_eq!(msg, "first panic");
+        let msg = panic_message(|| {
+            let _guard = string.inner_str_mut_checked();
+            panic!("{}", "second panic");
+        });
+        assert_eq!(msg, "second panic");
+    }
+}
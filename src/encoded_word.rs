@@ -0,0 +1,458 @@
+//! Encoded words ([RFC 2047]) for non us-ascii text in mail headers
+//!
+//! The encoder always uses the `utf-8` charset and automatically
+//! chooses between the `Q` and `B` encoding depending on which one
+//! produces the shorter output. Longer input is split into multiple
+//! words (separated by a single space) each of which is at most 75
+//! chars long and contains only whole chars.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::encoded_word;
+//! use soft_ascii_string::SoftAsciiStr;
+//!
+//! let encoded = encoded_word::encode("Hello Wörld");
+//! assert_eq!(encoded, "=?utf-8?Q?Hello_W=C3=B6rld?=");
+//!
+//! let header = SoftAsciiStr::from_str("Re: =?utf-8?B?5L2g5aW9?= =?utf-8?Q?_Welt?=").unwrap();
+//! assert_eq!(encoded_word::decode(header).unwrap(), "Re: 你好 Welt");
+//! ```
+//!
+//! [RFC 2047]: https://tools.ietf.org/html/rfc2047
+use core::fmt::{self, Display};
+use core::str;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use base64;
+use hex;
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+/// the maximal length of a single encoded word
+pub const MAX_WORD_LEN: usize = 75;
+
+/// `"=?utf-8?Q?"` and `"?="`
+const WORD_OVERHEAD: usize = 12;
+const MAX_PAYLOAD_LEN: usize = MAX_WORD_LEN - WORD_OVERHEAD;
+
+/// the encoding used for the text of an encoded word
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// quoted-printable like encoding, readable for mostly ascii text
+    Q,
+    /// base64 encoding, shorter for mostly non ascii text
+    B
+}
+
+impl Encoding {
+
+    /// chooses the encoding which produces the shorter output for `input`
+    pub fn choose(input: &str) -> Encoding {
+        let q_len: usize = input.bytes().map(q_encoded_len).sum();
+        let b_len = input.len().div_ceil(3) * 4;
+        if q_len <= b_len {
+            Encoding::Q
+        } else {
+            Encoding::B
+        }
+    }
+
+    fn as_char(self) -> SoftAsciiChar {
+        match self {
+            Encoding::Q => SoftAsciiChar::rewrap('Q'),
+            Encoding::B => SoftAsciiChar::rewrap('B')
+        }
+    }
+}
+
+/// chars which can be used unencoded in the `Q` encoding in every header context
+fn is_q_safe(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!*+-/".contains(&byte)
+}
+
+fn q_encoded_len(byte: u8) -> usize {
+    if byte == b' ' || is_q_safe(byte) { 1 } else { 3 }
+}
+
+/// encodes `input` as one or more encoded words using the shorter encoding
+pub fn encode(input: &str) -> SoftAsciiString {
+    encode_with(input, Encoding::choose(input))
+}
+
+/// encodes `input` as one or more encoded words using the given encoding
+pub fn encode_with(input: &str, encoding: Encoding) -> SoftAsciiString {
+    let mut out = SoftAsciiString::with_capacity(input.len() * 3 / 2 + WORD_OVERHEAD);
+    encode_into(input, encoding, &mut out);
+    out
+}
+
+/// encodes `input` as one or more encoded words appending them to `out`
+///
+/// An empty input is encoded as a single empty word.
+pub fn encode_into(input: &str, encoding: Encoding, out: &mut SoftAsciiString) {
+    // the limit of B words is given in (unencoded) bytes
+    let limit = match encoding {
+        Encoding::Q => MAX_PAYLOAD_LEN,
+        Encoding::B => MAX_PAYLOAD_LEN / 4 * 3
+    };
+    let mut chunk_start = 0;
+    let mut chunk_len = 0;
+    for (idx, ch) in input.char_indices() {
+        let len = match encoding {
+            Encoding::Q => input[idx..idx + ch.len_utf8()].bytes().map(q_encoded_len).sum(),
+            Encoding::B => ch.len_utf8()
+        };
+        if chunk_len + len > limit {
+            write_word(&input[chunk_start..idx], encoding, chunk_start == 0, out);
+            chunk_start = idx;
+            chunk_len = 0;
+        }
+        chunk_len += len;
+    }
+    if chunk_start < input.len() || input.is_empty() {
+        write_word(&input[chunk_start..], encoding, chunk_start == 0, out);
+    }
+}
+
+fn write_word(chunk: &str, encoding: Encoding, first: bool, out: &mut SoftAsciiString) {
+    if !first {
+        out.push(SoftAsciiChar::rewrap(' '));
+    }
+    out.push_str(SoftAsciiStr::rewrap("=?utf-8?"));
+    out.push(encoding.as_char());
+    out.push(SoftAsciiChar::rewrap('?'));
+    match encoding {
+        Encoding::Q => write_q(chunk.as_bytes(), out),
+        Encoding::B => base64::encode_into(chunk.as_bytes(), base64::Config::STANDARD, out)
+    }
+    out.push_str(SoftAsciiStr::rewrap("?="));
+}
+
+fn write_q(bytes: &[u8], out: &mut SoftAsciiString) {
+    for &byte in bytes {
+        if byte == b' ' {
+            out.push(SoftAsciiChar::rewrap('_'));
+        } else if is_q_safe(byte) {
+            out.push(SoftAsciiChar::rewrap(byte as char));
+        } else {
+            out.push(SoftAsciiChar::rewrap('='));
+            out.push(SoftAsciiChar::rewrap(hex::UPPER_DIGITS[(byte >> 4) as usize] as char));
+            out.push(SoftAsciiChar::rewrap(hex::UPPER_DIGITS[(byte & 0xf) as usize] as char));
+        }
+    }
+}
+
+/// Error returned if decoding encoded words failed
+///
+/// The contained offset is the byte offset of the start of the
+/// word in the input.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EncodedWordError {
+    /// the charset of the word is not supported
+    ///
+    /// Supported are `utf-8`, `us-ascii` and `iso-8859-1`.
+    UnsupportedCharset(usize),
+    /// the encoding of the word is neither `Q` nor `B`
+    UnknownEncoding(usize),
+    /// the word contains an invalid `=XX` escape
+    InvalidQ(usize),
+    /// the word contains invalid base64
+    InvalidB(usize),
+    /// the decoded text of the word (and all adjacent words) is not valid utf-8/us-ascii
+    InvalidText(usize)
+}
+
+impl Display for EncodedWordError {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EncodedWordError::UnsupportedCharset(idx) =>
+                write!(fter, "unsupported charset in encoded word at byte offset {}", idx),
+            EncodedWordError::UnknownEncoding(idx) =>
+                write!(fter, "unknown encoding in encoded word at byte offset {}", idx),
+            EncodedWordError::InvalidQ(idx) =>
+                write!(fter, "invalid escape in encoded word at byte offset {}", idx),
+            EncodedWordError::InvalidB(idx) =>
+                write!(fter, "invalid base64 in encoded word at byte offset {}", idx),
+            EncodedWordError::InvalidText(idx) =>
+                write!(fter, "encoded word at byte offset {} does not match its charset", idx)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for EncodedWordError {
+    fn description(&self) -> &str {
+        "decoding encoded word failed"
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Charset {
+    Utf8,
+    Ascii,
+    Latin1
+}
+
+impl Charset {
+    fn parse(name: &str) -> Option<Charset> {
+        // RFC 2231 allows a language suffix like `utf-8*en`
+        let name = name.split('*').next().unwrap_or(name);
+        if name.eq_ignore_ascii_case("utf-8") || name.eq_ignore_ascii_case("utf8") {
+            Some(Charset::Utf8)
+        } else if name.eq_ignore_ascii_case("us-ascii") {
+            Some(Charset::Ascii)
+        } else if name.eq_ignore_ascii_case("iso-8859-1") || name.eq_ignore_ascii_case("latin1") {
+            Some(Charset::Latin1)
+        } else {
+            None
+        }
+    }
+}
+
+/// a syntactically valid encoded word
+struct Word<'a> {
+    charset: &'a str,
+    encoding: &'a str,
+    text: &'a str,
+    end: usize
+}
+
+/// parses `=?charset?encoding?text?=` starting at `start`
+fn parse_word(input: &str, start: usize) -> Option<Word<'_>> {
+    let rest = input[start..].strip_prefix("=?")?;
+    let is_token_char = |ch: char| !ch.is_whitespace() && ch != '?';
+    let charset_len = rest.find(|ch| !is_token_char(ch))?;
+    let (charset, rest) = rest.split_at(charset_len);
+    let rest = rest.strip_prefix('?')?;
+    let encoding_len = rest.find(|ch| !is_token_char(ch))?;
+    let (encoding, rest) = rest.split_at(encoding_len);
+    let rest = rest.strip_prefix('?')?;
+    let text_len = rest.find(|ch| !is_token_char(ch))?;
+    let (text, rest) = rest.split_at(text_len);
+    rest.strip_prefix("?=")?;
+    if charset.is_empty() || encoding.is_empty() {
+        return None;
+    }
+    let end = start + 2 + charset.len() + 1 + encoding.len() + 1 + text.len() + 2;
+    Some(Word { charset, encoding, text, end })
+}
+
+fn decode_q(text: &str, out: &mut Vec<u8>) -> Option<()> {
+    let bytes = text.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'=' => {
+                let high = hex::digit_value(*bytes.get(idx + 1)?)?;
+                let low = hex::digit_value(*bytes.get(idx + 2)?)?;
+                out.push((high << 4) | low);
+                idx += 3;
+            },
+            b'_' => {
+                out.push(b' ');
+                idx += 1;
+            },
+            byte => {
+                out.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    Some(())
+}
+
+fn decode_b(text: &str, out: &mut Vec<u8>) -> Option<()> {
+    // padding is often omitted in the wild
+    // (`is_multiple_of` needs rust 1.87, the MSRV is 1.74)
+    #[allow(clippy::manual_is_multiple_of)]
+    let config = if text.len() % 4 == 0 {
+        base64::Config::STANDARD
+    } else {
+        base64::Config::STANDARD_NO_PAD
+    };
+    base64::decode_into(SoftAsciiStr::from_str(text).ok()?, config, out).ok()
+}
+
+/// decoded bytes of adjacent encoded words with the same charset
+struct Pending {
+    bytes: Vec<u8>,
+    charset: Charset,
+    offset: usize
+}
+
+impl Pending {
+    fn flush_into(&mut self, out: &mut String) -> Result<(), EncodedWordError> {
+        match self.charset {
+            Charset::Utf8 | Charset::Ascii => {
+                let text = str::from_utf8(&self.bytes)
+                    .map_err(|_| EncodedWordError::InvalidText(self.offset))?;
+                if self.charset == Charset::Ascii && !text.is_ascii() {
+                    return Err(EncodedWordError::InvalidText(self.offset));
+                }
+                out.push_str(text);
+            },
+            Charset::Latin1 => out.extend(self.bytes.iter().map(|&byte| byte as char))
+        }
+        self.bytes.clear();
+        Ok(())
+    }
+}
+
+/// decodes all encoded words in `input`
+///
+/// Text outside of encoded words is kept as is, except for whitespace
+/// between two encoded words which is removed. Malformed encoded words
+/// are treated as normal text, while syntactically valid words with an
+/// unsupported charset or invalid content result in an error.
+pub fn decode(input: &SoftAsciiStr) -> Result<String, EncodedWordError> {
+    let input = input.as_str();
+    let mut out = String::with_capacity(input.len());
+    let mut pending = Pending { bytes: Vec::new(), charset: Charset::Utf8, offset: 0 };
+    let mut plain_start = 0;
+    let mut after_word = false;
+    let mut idx = 0;
+    while idx < input.len() {
+        let word = match input.as_bytes()[idx] {
+            b'=' => parse_word(input, idx),
+            _ => None
+        };
+        let word = match word {
+            Some(word) => word,
+            None => {
+                idx += 1;
+                continue;
+            }
+        };
+
+        let charset = Charset::parse(word.charset)
+            .ok_or(EncodedWordError::UnsupportedCharset(idx))?;
+        let between = &input[plain_start..idx];
+        let joins_previous = after_word && between.chars().all(char::is_whitespace);
+        if !joins_previous || charset != pending.charset {
+            pending.flush_into(&mut out)?;
+        }
+        if !joins_previous {
+            out.push_str(between);
+        }
+        if pending.bytes.is_empty() {
+            pending.charset = charset;
+            pending.offset = idx;
+        }
+
+        if word.encoding.eq_ignore_ascii_case("q") {
+            decode_q(word.text, &mut pending.bytes)
+                .ok_or(EncodedWordError::InvalidQ(idx))?;
+        } else if word.encoding.eq_ignore_ascii_case("b") {
+            decode_b(word.text, &mut pending.bytes)
+                .ok_or(EncodedWordError::InvalidB(idx))?;
+        } else {
+            return Err(EncodedWordError::UnknownEncoding(idx));
+        }
+
+        idx = word.end;
+        plain_start = word.end;
+        after_word = true;
+    }
+    pending.flush_into(&mut out)?;
+    out.push_str(&input[plain_start..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use macros::soft;
+    use super::{encode, encode_with, decode, Encoding, EncodedWordError, MAX_WORD_LEN};
+
+    #[test]
+    fn choose_encoding() {
+        assert_eq!(Encoding::choose("Hello Wörld"), Encoding::Q);
+        assert_eq!(Encoding::choose("你好"), Encoding::B);
+    }
+
+    #[test]
+    fn encode_q() {
+        assert_eq!(encode("Hello Wörld?"), "=?utf-8?Q?Hello_W=C3=B6rld=3F?=");
+        assert_eq!(encode_with("a_b=c", Encoding::Q), "=?utf-8?Q?a=5Fb=3Dc?=");
+    }
+
+    #[test]
+    fn encode_b() {
+        assert_eq!(encode("你好"), "=?utf-8?B?5L2g5aW9?=");
+        assert_eq!(encode_with("ab", Encoding::B), "=?utf-8?B?YWI=?=");
+        assert_eq!(encode_with("a", Encoding::B), "=?utf-8?B?YQ==?=");
+    }
+
+    #[test]
+    fn encode_empty() {
+        assert_eq!(encode(""), "=?utf-8?Q??=");
+    }
+
+    #[test]
+    fn long_input_is_split_at_char_boundaries() {
+        let inputs = [
+            "Sehr geehrte Damen und Herren, äöü ÄÖÜ ß sind deutsche Umlaute und Sonderzeichen",
+            "日本語のテキストはとても長くなることがあります。日本語のテキストはとても長くなることがあります。"
+        ];
+        for input in inputs.iter() {
+            let encoded = encode(input);
+            let words: Vec<_> = encoded.as_str().split(' ').collect();
+            assert!(words.len() > 1, "{}", encoded);
+            for word in &words {
+                assert!(word.len() <= MAX_WORD_LEN, "{}", word);
+                // each word has to decode on its own
+                assert_ok!(decode(soft(word)));
+            }
+            assert_eq!(assert_ok!(decode(&encoded)), *input);
+        }
+    }
+
+    #[test]
+    fn decode_mixed_text() {
+        let input = soft("a =?utf-8?q?b?=  =?UTF-8?Q?c_?=\r\n =?utf-8?B?w6Q=?= d=?x e?=");
+        assert_eq!(assert_ok!(decode(input)), "a bc ä d=?x e?=");
+    }
+
+    #[test]
+    fn decode_other_charsets() {
+        assert_eq!(assert_ok!(decode(soft("=?iso-8859-1?Q?Gr=FC=DFe?="))), "Grüße");
+        assert_eq!(assert_ok!(decode(soft("=?us-ascii*en?Q?hy?="))), "hy");
+    }
+
+    #[test]
+    fn decode_chars_split_across_words() {
+        assert_eq!(assert_ok!(decode(soft("=?utf-8?Q?=C3?= =?utf-8?Q?=A4?="))), "ä");
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            assert_err!(decode(soft("a =?koi8-r?Q?x?="))),
+            EncodedWordError::UnsupportedCharset(2)
+        );
+        assert_eq!(
+            assert_err!(decode(soft("a =?utf-8?X?x?="))),
+            EncodedWordError::UnknownEncoding(2)
+        );
+        assert_eq!(
+            assert_err!(decode(soft("a =?utf-8?Q?=4?="))),
+            EncodedWordError::InvalidQ(2)
+        );
+        assert_eq!(
+            assert_err!(decode(soft("a =?utf-8?B?Y*==?="))),
+            EncodedWordError::InvalidB(2)
+        );
+        assert_eq!(
+            assert_err!(decode(soft("a =?utf-8?Q?=C3?= b"))),
+            EncodedWordError::InvalidText(2)
+        );
+        assert_eq!(
+            assert_err!(decode(soft("=?us-ascii?Q?=C3=A4?="))),
+            EncodedWordError::InvalidText(0)
+        );
+    }
+}
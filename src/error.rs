@@ -1,9 +1,57 @@
-use std::fmt::{self, Debug};
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use core::str;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "std")]
 use std::error::Error;
 
+#[cfg(feature = "track-unchecked")]
+use core::panic::Location;
+
+#[cfg(feature = "track-unchecked")]
+use track::CreatedAt;
+use validate;
+
 /// Error returned if FromStr failed
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-pub struct StringFromStrError;
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct StringFromStrError {
+    violation: Option<Violation>
+}
+
+impl StringFromStrError {
+
+    /// creates a new StringFromStrError without a known position
+    pub fn new() -> Self {
+        StringFromStrError { violation: None }
+    }
+
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) fn from_violation(violation: Violation) -> Self {
+        StringFromStrError { violation: Some(violation) }
+    }
+
+    /// returns the index of the first non us-ascii char
+    ///
+    /// i.e. the source is us-ascii up to (excluding) this byte offset,
+    /// `None` is returned if the error was created with `new`
+    pub fn valid_up_to(&self) -> Option<usize> {
+        self.violation.map(|violation| violation.valid_up_to)
+    }
+
+    /// returns the first non us-ascii char of the source
+    ///
+    /// `None` is returned if the error was created with `new`
+    pub fn invalid_char(&self) -> Option<char> {
+        self.violation.map(|violation| violation.invalid_char)
+    }
+}
+
+#[cfg(feature = "std")]
 impl Error for StringFromStrError {
     fn description(&self) -> &str {
         "&str does contain non us-ascii chars and can not be converted to a SoftAsciiString"
@@ -12,14 +60,75 @@ impl Error for StringFromStrError {
 
 impl fmt::Display for StringFromStrError {
     fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
-        write!(fter, "{}", self.description())
+        match self.violation {
+            Some(ref violation) => write!(
+                fter,
+                "&str does contain non us-ascii char {:?} at byte offset {} \
+                 and can not be converted to a SoftAsciiString:\n{}",
+                violation.invalid_char, violation.valid_up_to, violation.snippet
+            ),
+            None => fter.write_str(
+                "&str does contain non us-ascii chars and can not be converted to a SoftAsciiString"
+            )
+        }
+    }
+}
+
+/// Error returned if a value does not fit into the remaining capacity of a SoftAsciiArrayString
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CapacityError<T> {
+    element: T
+}
+
+impl<T> CapacityError<T> {
+
+    pub(crate) fn new(element: T) -> Self {
+        CapacityError { element }
+    }
+
+    /// returns a reference to the value which did not fit
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+
+    /// returns the value which did not fit
+    pub fn into_element(self) -> T {
+        self.element
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Debug> Error for CapacityError<T> {
+    fn description(&self) -> &str {
+        "insufficient capacity"
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        fter.write_str("insufficient capacity")
     }
 }
 
 /// Error returned if creating a SoftAsciiStr/SoftAsciiString failed
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+///
+/// Errors are compared and hashed by their source only.
+#[derive(Debug, Clone)]
 pub struct FromSourceError<S: Debug> {
-    source: S
+    source: S,
+    position: Position,
+    #[cfg(feature = "track-unchecked")]
+    created_at: CreatedAt
+}
+
+/// what is known about the non us-ascii chars of the source of a `FromSourceError`
+#[derive(Debug, Copy, Clone)]
+enum Position {
+    /// the source was not searched (`FromSourceError::new`)
+    Unknown,
+    /// the source is us-ascii, it has the given length
+    NotFound(usize),
+    Found(Violation)
 }
 
 impl<S> FromSourceError<S>
@@ -27,8 +136,47 @@ impl<S> FromSourceError<S>
 {
 
     /// creates a new FromSourceError
+    ///
+    /// The position of the first non us-ascii char is unknown, use
+    /// `from_source` to search for it.
     pub fn new(source: S) -> Self {
-        FromSourceError { source }
+        FromSourceError::with_position(source, Position::Unknown)
+    }
+
+    /// creates a new FromSourceError searching for the first non us-ascii char of the source
+    ///
+    /// If the source (unexpectedly) is us-ascii, `valid_up_to` will be
+    /// the length of the source and `invalid_char` will be `None`.
+    pub fn from_source(source: S) -> Self
+        where S: ErrorSource
+    {
+        let position = {
+            let mut buf = [0u8; 4];
+            let source_str = source.as_source_str(&mut buf);
+            match Violation::find(source_str) {
+                Some(violation) => Position::Found(violation),
+                None => Position::NotFound(source_str.len())
+            }
+        };
+        FromSourceError::with_position(source, position)
+    }
+
+    pub(crate) fn from_violation(source: S, violation: Violation) -> Self {
+        FromSourceError::with_position(source, Position::Found(violation))
+    }
+
+    fn with_position(source: S, position: Position) -> Self {
+        FromSourceError {
+            source, position,
+            #[cfg(feature = "track-unchecked")]
+            created_at: CreatedAt::default()
+        }
+    }
+
+    #[cfg(feature = "track-unchecked")]
+    pub(crate) fn with_created_at(mut self, created_at: Option<&'static Location<'static>>) -> Self {
+        self.created_at = CreatedAt(created_at);
+        self
     }
 
     /// returns a reference to the source
@@ -49,10 +197,44 @@ impl<S> FromSourceError<S>
     pub fn into_source(self) -> S {
         self.source
     }
+
+    /// returns the index of the first non us-ascii char in the source
+    ///
+    /// i.e. the source is us-ascii up to (excluding) this byte offset,
+    /// `None` is returned if the error was created with `new`
+    pub fn valid_up_to(&self) -> Option<usize> {
+        match self.position {
+            Position::Unknown => None,
+            Position::NotFound(len) => Some(len),
+            Position::Found(violation) => Some(violation.valid_up_to)
+        }
+    }
+
+    /// returns the first non us-ascii char of the source
+    ///
+    /// `None` is returned if the error was created with `new` or the
+    /// source is us-ascii
+    pub fn invalid_char(&self) -> Option<char> {
+        match self.position {
+            Position::Found(violation) => Some(violation.invalid_char),
+            _ => None
+        }
+    }
+
+    /// returns where the unchecked value containing the non us-ascii char was created
+    ///
+    /// This is only set for errors returned by `SoftAsciiStr::revalidate_soft_constraint`
+    /// and only if the value was created by a tracked unchecked constructor, see `track`.
+    /// It's ignored when comparing or hashing errors.
+    #[cfg(feature = "track-unchecked")]
+    pub fn created_at(&self) -> Option<&'static Location<'static>> {
+        self.created_at.0
+    }
 }
 
 
 
+#[cfg(feature = "std")]
 impl<S> Error for FromSourceError<S>
     where S: Debug
 {
@@ -62,10 +244,332 @@ impl<S> Error for FromSourceError<S>
     }
 }
 
+impl<S> PartialEq for FromSourceError<S>
+    where S: Debug + PartialEq
+{
+    fn eq(&self, other: &FromSourceError<S>) -> bool {
+        self.source == other.source
+    }
+}
+
+impl<S> Eq for FromSourceError<S>
+    where S: Debug + Eq
+{}
+
+impl<S> Hash for FromSourceError<S>
+    where S: Debug + Hash
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.source.hash(state)
+    }
+}
+
 impl<S> fmt::Display for FromSourceError<S>
     where S: Debug
 {
     fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
-        write!(fter, "source contains non us-ascii chars: {:?}", self.source)
+        match self.position {
+            Position::Unknown =>
+                write!(fter, "source contains non us-ascii chars: {:?}", self.source),
+            Position::NotFound(_) =>
+                fter.write_str("source does not contain non us-ascii chars"),
+            Position::Found(ref violation) => write!(
+                fter,
+                "source contains non us-ascii char {:?} at byte offset {}:\n{}",
+                violation.invalid_char, violation.valid_up_to, violation.snippet
+            )
+        }
     }
-}
\ No newline at end of file
+}
+
+/// types which can be the source of a `FromSourceError` created with `FromSourceError::from_source`
+pub trait ErrorSource: Debug {
+    /// returns the source as `str`, `buf` can be used to encode single chars
+    fn as_source_str<'a>(&'a self, buf: &'a mut [u8; 4]) -> &'a str;
+}
+
+impl ErrorSource for char {
+    fn as_source_str<'a>(&'a self, buf: &'a mut [u8; 4]) -> &'a str {
+        self.encode_utf8(buf)
+    }
+}
+
+impl ErrorSource for &str {
+    fn as_source_str<'a>(&'a self, _buf: &'a mut [u8; 4]) -> &'a str {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ErrorSource for String {
+    fn as_source_str<'a>(&'a self, _buf: &'a mut [u8; 4]) -> &'a str {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ErrorSource for Box<str> {
+    fn as_source_str<'a>(&'a self, _buf: &'a mut [u8; 4]) -> &'a str {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'b> ErrorSource for Cow<'b, str> {
+    fn as_source_str<'a>(&'a self, _buf: &'a mut [u8; 4]) -> &'a str {
+        self
+    }
+}
+
+/// position of the first non us-ascii char in a source and a snippet around it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Violation {
+    valid_up_to: usize,
+    invalid_char: char,
+    snippet: Snippet
+}
+
+impl Violation {
+
+    /// searches for the first non us-ascii char, returns `None` if there is none
+    pub(crate) fn find(source: &str) -> Option<Violation> {
+        let valid_up_to = validate::first_non_ascii(source.as_bytes())?;
+        Some(Violation::at(source, valid_up_to))
+    }
+
+    pub(crate) fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// creates the violation for the (non us-ascii) char starting at `valid_up_to`
+    pub(crate) fn at(source: &str, valid_up_to: usize) -> Violation {
+        let invalid_char = source[valid_up_to..].chars().next()
+            .expect("[BUG] violation has to point to a char");
+        Violation {
+            valid_up_to, invalid_char,
+            snippet: Snippet::new(source, valid_up_to, invalid_char)
+        }
+    }
+}
+
+/// number of bytes shown before/after the non us-ascii char
+const SNIPPET_CONTEXT: usize = 16;
+const SNIPPET_BUF_LEN: usize = 2 * SNIPPET_CONTEXT + 4;
+
+/// a short excerpt of the source around the first non us-ascii char
+///
+/// It's stored inline so that the errors neither need to allocate
+/// nor need to be able to access the source as `str`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+struct Snippet {
+    buf: [u8; SNIPPET_BUF_LEN],
+    len: u8,
+    /// offset of the non us-ascii char in `buf`
+    mark: u8,
+    truncated_start: bool,
+    truncated_end: bool
+}
+
+impl Snippet {
+
+    fn new(source: &str, valid_up_to: usize, invalid_char: char) -> Snippet {
+        // everything before valid_up_to is ascii so every index is a char boundary
+        let start = valid_up_to.saturating_sub(SNIPPET_CONTEXT);
+        let max_end = valid_up_to + invalid_char.len_utf8() + SNIPPET_CONTEXT;
+        let mut end = source.len().min(max_end);
+        while !source.is_char_boundary(end) {
+            end -= 1;
+        }
+        let excerpt = &source.as_bytes()[start..end];
+        let mut buf = [0u8; SNIPPET_BUF_LEN];
+        buf[..excerpt.len()].copy_from_slice(excerpt);
+        Snippet {
+            buf,
+            len: excerpt.len() as u8,
+            mark: (valid_up_to - start) as u8,
+            truncated_start: start > 0,
+            truncated_end: end < source.len()
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // buf is always created from whole chars of a str
+        str::from_utf8(&self.buf[..self.len as usize]).unwrap_or("")
+    }
+}
+
+impl Debug for Snippet {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        fter.debug_tuple("Snippet")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+/// writes the snippet on one line and a caret marking the non us-ascii char on the next
+impl fmt::Display for Snippet {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        const INDENT: &str = "    ";
+        const ELLIPSIS: &str = "...";
+
+        let snippet = self.as_str();
+        let mark = self.mark as usize;
+        let mut caret_offset = 0;
+
+        fter.write_str(INDENT)?;
+        if self.truncated_start {
+            fter.write_str(ELLIPSIS)?;
+            caret_offset += ELLIPSIS.len();
+        }
+        for (idx, ch) in snippet.char_indices() {
+            // everything before the mark is ascii, so the width of the
+            // written text is known, non us-ascii chars which would not take
+            // up a column of their own (or reorder the line) are escaped
+            let width = if ch.is_control() || is_invisible(ch) {
+                let mut width = 0;
+                for escaped in ch.escape_default() {
+                    fter.write_fmt(format_args!("{}", escaped))?;
+                    width += 1;
+                }
+                width
+            } else {
+                fter.write_fmt(format_args!("{}", ch))?;
+                1
+            };
+            if idx < mark {
+                caret_offset += width;
+            }
+        }
+        if self.truncated_end {
+            fter.write_str(ELLIPSIS)?;
+        }
+        fter.write_str("\n")?;
+        fter.write_str(INDENT)?;
+        for _ in 0..caret_offset {
+            fter.write_str(" ")?;
+        }
+        fter.write_str("^")
+    }
+}
+
+/// returns true for (some) zero width chars, e.g. combining marks and bidi controls
+fn is_invisible(ch: char) -> bool {
+    matches!(ch as u32,
+        0x0300..=0x036F | 0x0483..=0x0489 | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F | 0x202A..=0x202E | 0x2060..=0x206F | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F | 0xFE20..=0xFE2F | 0xFEFF | 0xE0000..=0xE007F
+        | 0xE0100..=0xE01EF)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FromSourceError, StringFromStrError, Violation};
+
+    #[test]
+    fn position_of_first_non_ascii_char() {
+        let err = FromSourceError::from_source("abc↓de←");
+        assert_eq!(err.valid_up_to(), Some(3));
+        assert_eq!(err.invalid_char(), Some('↓'));
+    }
+
+    #[test]
+    fn position_of_char_source() {
+        let err = FromSourceError::from_source('↓');
+        assert_eq!(err.valid_up_to(), Some(0));
+        assert_eq!(err.invalid_char(), Some('↓'));
+    }
+
+    #[test]
+    fn ascii_source() {
+        let err = FromSourceError::from_source("abc");
+        assert_eq!(err.valid_up_to(), Some(3));
+        assert_eq!(err.invalid_char(), None);
+        assert_eq!(err.to_string(), "source does not contain non us-ascii chars");
+    }
+
+    #[test]
+    fn unknown_position() {
+        let err = FromSourceError::new("ab↓c");
+        assert_eq!(err.valid_up_to(), None);
+        assert_eq!(err.invalid_char(), None);
+        assert_eq!(err.to_string(), "source contains non us-ascii chars: \"ab↓c\"");
+        // errors are equal if their sources are
+        assert_eq!(err, FromSourceError::from_source("ab↓c"));
+    }
+
+    #[test]
+    fn display_short_source() {
+        let err = FromSourceError::from_source("ab↓c");
+        assert_eq!(
+            err.to_string(),
+            "source contains non us-ascii char '↓' at byte offset 2:\n    ab↓c\n      ^"
+        );
+    }
+
+    #[test]
+    fn display_truncates_long_source() {
+        let source = "0123456789abcdefghijklmnopqrstuvwxyz↓0123456789abcdefghijklmnopqrstuvwxyz";
+        let err = FromSourceError::from_source(source);
+        assert_eq!(
+            err.to_string(),
+            concat!(
+                "source contains non us-ascii char '↓' at byte offset 36:\n",
+                "    ...klmnopqrstuvwxyz↓0123456789abcdef...\n",
+                "                       ^"
+            )
+        );
+    }
+
+    #[test]
+    fn display_escapes_control_chars() {
+        let err = FromSourceError::from_source("a\r\nb↓");
+        assert_eq!(
+            err.to_string(),
+            "source contains non us-ascii char '↓' at byte offset 4:\n    a\\r\\nb↓\n          ^"
+        );
+    }
+
+    #[test]
+    fn display_escapes_zero_width_chars() {
+        let err = FromSourceError::from_source("abe\u{301}\u{202e}x");
+        assert_eq!(
+            err.to_string(),
+            concat!(
+                "source contains non us-ascii char '\\u{301}' at byte offset 3:\n",
+                "    abe\\u{301}\\u{202e}x\n",
+                "       ^"
+            )
+        );
+    }
+
+    #[test]
+    fn snippet_does_not_split_chars() {
+        let err = FromSourceError::from_source("↓0123456789abcde↓↓");
+        let display = err.to_string();
+        assert!(display.contains("↓0123456789abcde..."), "{}", display);
+    }
+
+    #[test]
+    fn string_from_str_error() {
+        let err = StringFromStrError::from_violation(Violation::find("ab←").unwrap());
+        assert_eq!(err.valid_up_to(), Some(2));
+        assert_eq!(err.invalid_char(), Some('←'));
+        assert_eq!(
+            err.to_string(),
+            concat!(
+                "&str does contain non us-ascii char '←' at byte offset 2 ",
+                "and can not be converted to a SoftAsciiString:\n",
+                "    ab←\n",
+                "      ^"
+            )
+        );
+        let err = StringFromStrError::new();
+        assert_eq!(err, StringFromStrError::default());
+        assert_eq!(err.valid_up_to(), None);
+        assert_eq!(err.invalid_char(), None);
+        assert_eq!(
+            err.to_string(),
+            "&str does contain non us-ascii chars and can not be converted to a SoftAsciiString"
+        );
+    }
+}
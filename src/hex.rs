@@ -0,0 +1,139 @@
+//! Hex (base16) encoding and decoding
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::hex::{self, Case};
+//!
+//! let encoded = hex::encode(b"\x01\xab", Case::Lower);
+//! assert_eq!(encoded, "01ab");
+//! assert_eq!(hex::encode(b"\x01\xab", Case::Upper), "01AB");
+//!
+//! assert_eq!(hex::decode(&encoded).unwrap(), b"\x01\xab");
+//! ```
+use core::fmt::{self, Display};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+pub(crate) const UPPER_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+const LOWER_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// the case of the hex digits `a`-`f` in the output
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// `0123456789abcdef`
+    Lower,
+    /// `0123456789ABCDEF`
+    Upper
+}
+
+/// returns the value of a (upper or lower case) hex digit
+pub(crate) fn digit_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None
+    }
+}
+
+/// hex encodes `input`
+pub fn encode(input: &[u8], case: Case) -> SoftAsciiString {
+    let mut out = SoftAsciiString::with_capacity(input.len() * 2);
+    encode_into(input, case, &mut out);
+    out
+}
+
+/// hex encodes `input` appending the result to `out`
+pub fn encode_into(input: &[u8], case: Case, out: &mut SoftAsciiString) {
+    let digits = match case {
+        Case::Lower => LOWER_DIGITS,
+        Case::Upper => UPPER_DIGITS
+    };
+    for &byte in input {
+        out.push(SoftAsciiChar::rewrap(digits[(byte >> 4) as usize] as char));
+        out.push(SoftAsciiChar::rewrap(digits[(byte & 0xf) as usize] as char));
+    }
+}
+
+/// Error returned if hex decoding failed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HexError {
+    /// the char at the given byte offset is not a hex digit
+    InvalidDigit(usize),
+    /// the input has an odd number of digits
+    OddLength
+}
+
+impl Display for HexError {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HexError::InvalidDigit(idx) =>
+                write!(fter, "invalid hex digit at byte offset {}", idx),
+            HexError::OddLength =>
+                write!(fter, "hex input has an odd number of digits")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for HexError {
+    fn description(&self) -> &str {
+        "hex decoding failed"
+    }
+}
+
+/// decodes the hex encoded `input`, both upper and lower case digits are accepted
+pub fn decode(input: &SoftAsciiStr) -> Result<Vec<u8>, HexError> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len() / 2);
+    for (pair_idx, pair) in bytes.chunks(2).enumerate() {
+        let idx = pair_idx * 2;
+        let high = digit_value(pair[0]).ok_or(HexError::InvalidDigit(idx))?;
+        let low = match pair.get(1) {
+            Some(&low) => digit_value(low).ok_or(HexError::InvalidDigit(idx + 1))?,
+            None => return Err(HexError::OddLength)
+        };
+        out.push((high << 4) | low);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use macros::soft;
+    use soft_str::SoftAsciiStr;
+    use super::{encode, decode, Case, HexError};
+
+    #[test]
+    fn encoding() {
+        assert_eq!(encode(b"", Case::Lower), "");
+        assert_eq!(encode(b"\x00\x0f\xf0\xff", Case::Lower), "000ff0ff");
+        assert_eq!(encode(b"\x00\x0f\xf0\xff", Case::Upper), "000FF0FF");
+    }
+
+    #[test]
+    fn decoding() {
+        assert_eq!(assert_ok!(decode(soft("000fF0Ff"))), b"\x00\x0f\xf0\xff");
+        assert_eq!(assert_ok!(decode(soft(""))), b"");
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(assert_err!(decode(soft("00g0"))), HexError::InvalidDigit(2));
+        assert_eq!(assert_err!(decode(soft("000"))), HexError::OddLength);
+        // `rewrap` isn't checked with `check-unchecked`
+        assert_eq!(assert_err!(decode(SoftAsciiStr::rewrap("0ä"))), HexError::InvalidDigit(1));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(assert_ok!(decode(&encode(&bytes, Case::Upper))), bytes);
+    }
+}
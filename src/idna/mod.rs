@@ -0,0 +1,404 @@
+//! IDNA ([RFC 3490]) domain name encoding producing `SoftAsciiString`s
+//!
+//! This implements the `ToASCII` and `ToUnicode` label operations on
+//! top of [punycode](punycode/index.html) and convenience functions
+//! applying them to every label of a domain.
+//!
+//! # Limitations
+//!
+//! The nameprep profile ([RFC 3491]) is not fully implemented, instead
+//! labels are only lowercased. Input which would be changed by other
+//! nameprep mappings (or which is prohibited by nameprep) is encoded as
+//! is, so callers which need full IDNA2003/UTS #46 mappings have to
+//! apply them before calling `label_to_ascii`/`domain_to_ascii`.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::SoftAsciiStr;
+//! use soft_ascii_string::idna::{domain_to_ascii, domain_to_unicode, Options};
+//!
+//! let ascii = domain_to_ascii("Bücher.example", Options::default()).unwrap();
+//! assert_eq!(ascii, "xn--bcher-kva.example");
+//!
+//! let unicode = domain_to_unicode(&ascii, Options::default()).unwrap();
+//! assert_eq!(unicode, "bücher.example");
+//! ```
+//!
+//! [RFC 3490]: https://tools.ietf.org/html/rfc3490
+//! [RFC 3491]: https://tools.ietf.org/html/rfc3491
+use core::fmt::{self, Display};
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+use self::punycode::PunycodeError;
+
+pub mod punycode;
+
+/// the ACE prefix which marks punycode encoded labels
+pub const ACE_PREFIX: &str = "xn--";
+
+/// the maximal length of a label in bytes (RFC 1034)
+pub const MAX_LABEL_LEN: usize = 63;
+
+/// the maximal length of a domain in bytes, excluding a trailing dot (RFC 1034)
+pub const MAX_DOMAIN_LEN: usize = 253;
+
+/// Options for the IDNA operations
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// if true labels are restricted to letters, digits and hyphens and may
+    /// not start or end with a hyphen (the `UseSTD3ASCIIRules` flag of RFC 3490)
+    ///
+    /// This should be enabled for host names and mail domains.
+    pub use_std3_ascii_rules: bool
+}
+
+impl Default for Options {
+    /// `use_std3_ascii_rules` is enabled by default
+    fn default() -> Self {
+        Options { use_std3_ascii_rules: true }
+    }
+}
+
+/// Error returned if a IDNA operation failed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IdnaError {
+    /// a label (except the root label) is empty
+    EmptyLabel,
+    /// a label is longer than `MAX_LABEL_LEN` after encoding
+    LabelTooLong,
+    /// the domain is longer than `MAX_DOMAIN_LEN` after encoding
+    DomainTooLong,
+    /// a label starts or ends with a hyphen (with `use_std3_ascii_rules`)
+    HyphenAtLabelBoundary,
+    /// a label contains the given non letter-digit-hyphen ascii char
+    /// (with `use_std3_ascii_rules`)
+    InvalidAsciiChar(char),
+    /// a label which has to be encoded already starts with the ACE prefix
+    AcePrefixedInput,
+    /// a label in the `ToUnicode` input contained non us-ascii chars
+    NonAsciiInput,
+    /// punycode encoding/decoding failed
+    Punycode(PunycodeError),
+    /// re-encoding a decoded label did not produce the original label
+    NotReversible
+}
+
+impl From<PunycodeError> for IdnaError {
+    fn from(err: PunycodeError) -> Self {
+        IdnaError::Punycode(err)
+    }
+}
+
+impl Display for IdnaError {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IdnaError::EmptyLabel =>
+                write!(fter, "domain contains an empty label"),
+            IdnaError::LabelTooLong =>
+                write!(fter, "label is longer than {} bytes", MAX_LABEL_LEN),
+            IdnaError::DomainTooLong =>
+                write!(fter, "domain is longer than {} bytes", MAX_DOMAIN_LEN),
+            IdnaError::HyphenAtLabelBoundary =>
+                write!(fter, "label starts or ends with a hyphen"),
+            IdnaError::InvalidAsciiChar(ch) =>
+                write!(fter, "label contains invalid char {:?}", ch),
+            IdnaError::AcePrefixedInput =>
+                write!(fter, "non ascii label starts with the ACE prefix {:?}", ACE_PREFIX),
+            IdnaError::NonAsciiInput =>
+                write!(fter, "ace label contains non us-ascii chars"),
+            IdnaError::Punycode(ref err) => err.fmt(fter),
+            IdnaError::NotReversible =>
+                write!(fter, "decoded label does not encode to the original label")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for IdnaError {
+    fn description(&self) -> &str {
+        "IDNA encoding/decoding failed"
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            IdnaError::Punycode(ref err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+/// returns true if `ch` is one of the label separators of RFC 3490
+fn is_label_separator(ch: char) -> bool {
+    matches!(ch, '.' | '\u{3002}' | '\u{ff0e}' | '\u{ff61}')
+}
+
+fn has_ace_prefix(label: &str) -> bool {
+    label.len() >= ACE_PREFIX.len()
+        // compares bytes, the label might not have a char boundary there
+        && label.as_bytes()[..ACE_PREFIX.len()].eq_ignore_ascii_case(ACE_PREFIX.as_bytes())
+}
+
+fn check_std3_rules(label: &str) -> Result<(), IdnaError> {
+    if let Some(ch) = label.chars()
+        .find(|&ch| ch.is_ascii() && !(ch.is_ascii_alphanumeric() || ch == '-'))
+    {
+        return Err(IdnaError::InvalidAsciiChar(ch));
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+        return Err(IdnaError::HyphenAtLabelBoundary);
+    }
+    Ok(())
+}
+
+/// the `ToASCII` operation for a single label
+pub fn label_to_ascii(label: &str, options: Options) -> Result<SoftAsciiString, IdnaError> {
+    let mut out = SoftAsciiString::with_capacity(label.len());
+    label_to_ascii_into(label, options, &mut out)?;
+    Ok(out)
+}
+
+fn label_to_ascii_into(label: &str, options: Options, out: &mut SoftAsciiString)
+    -> Result<(), IdnaError>
+{
+    let start = out.len();
+    if label.is_ascii() {
+        if label.len() > MAX_LABEL_LEN {
+            return Err(IdnaError::LabelTooLong);
+        }
+        if options.use_std3_ascii_rules {
+            check_std3_rules(label)?;
+        }
+        out.push_str(SoftAsciiStr::rewrap(label));
+    } else {
+        // every char is encoded as at least one byte and lowercasing never
+        // removes chars, so longer labels can be rejected before encoding
+        if label.chars().count() > MAX_LABEL_LEN - ACE_PREFIX.len() {
+            return Err(IdnaError::LabelTooLong);
+        }
+        let prepared: String = label.chars().flat_map(char::to_lowercase).collect();
+        if options.use_std3_ascii_rules {
+            check_std3_rules(&prepared)?;
+        }
+        if has_ace_prefix(&prepared) {
+            return Err(IdnaError::AcePrefixedInput);
+        }
+        out.push_str(SoftAsciiStr::rewrap(ACE_PREFIX));
+        punycode::encode_into(&prepared, out)?;
+    }
+    let len = out.len() - start;
+    if len == 0 {
+        Err(IdnaError::EmptyLabel)
+    } else if len > MAX_LABEL_LEN {
+        Err(IdnaError::LabelTooLong)
+    } else {
+        Ok(())
+    }
+}
+
+/// the `ToUnicode` operation for a single label
+///
+/// Labels without the ACE prefix are returned unchanged.
+pub fn label_to_unicode(label: &SoftAsciiStr, options: Options) -> Result<String, IdnaError> {
+    let label = label.as_str();
+    if !label.is_ascii() {
+        return Err(IdnaError::NonAsciiInput);
+    }
+    if !has_ace_prefix(label) {
+        return Ok(String::from(label));
+    }
+    // decoding is quadratic in the worst case and the re-encoded label
+    // has to be as long as `label`, so too long labels are rejected first
+    if label.len() > MAX_LABEL_LEN {
+        return Err(IdnaError::LabelTooLong);
+    }
+    let decoded = punycode::decode(&label[ACE_PREFIX.len()..])?;
+    let reencoded = label_to_ascii(&decoded, options)?;
+    if !reencoded.as_str().eq_ignore_ascii_case(label) {
+        return Err(IdnaError::NotReversible);
+    }
+    Ok(decoded)
+}
+
+/// applies `label_to_ascii` to every label of `domain`
+///
+/// All label separators of RFC 3490 are accepted and replaced by `'.'`,
+/// a trailing separator (the root label) is kept.
+pub fn domain_to_ascii(domain: &str, options: Options) -> Result<SoftAsciiString, IdnaError> {
+    let mut out = SoftAsciiString::with_capacity(domain.len());
+    let mut labels = domain.split(is_label_separator).peekable();
+    while let Some(label) = labels.next() {
+        let is_last = labels.peek().is_none();
+        if label.is_empty() && is_last && !out.is_empty() {
+            // trailing dot of a fully qualified domain
+            break;
+        }
+        label_to_ascii_into(label, options, &mut out)?;
+        if !is_last {
+            out.push(SoftAsciiChar::rewrap('.'));
+        }
+    }
+    let len = if out.as_str().ends_with('.') { out.len() - 1 } else { out.len() };
+    if len > MAX_DOMAIN_LEN {
+        return Err(IdnaError::DomainTooLong);
+    }
+    Ok(out)
+}
+
+/// applies `label_to_unicode` to every label of `domain`
+pub fn domain_to_unicode(domain: &SoftAsciiStr, options: Options) -> Result<String, IdnaError> {
+    let mut out = String::with_capacity(domain.len());
+    let mut labels = domain.as_str().split('.').peekable();
+    while let Some(label) = labels.next() {
+        let is_last = labels.peek().is_none();
+        let is_root = is_last && !out.is_empty();
+        if label.is_empty() && !is_root {
+            return Err(IdnaError::EmptyLabel);
+        }
+        out.push_str(&label_to_unicode(SoftAsciiStr::rewrap(label), options)?);
+        if !is_last {
+            out.push('.');
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use soft_str::SoftAsciiStr;
+    use super::punycode::PunycodeError;
+    use super::{
+        label_to_ascii, label_to_unicode,
+        domain_to_ascii, domain_to_unicode,
+        IdnaError, Options, MAX_LABEL_LEN
+    };
+
+    const NO_STD3: Options = Options { use_std3_ascii_rules: false };
+
+    #[test]
+    fn ascii_label_is_unchanged() {
+        assert_eq!(assert_ok!(label_to_ascii("Example", Options::default())), "Example");
+    }
+
+    #[test]
+    fn non_ascii_label_is_encoded() {
+        assert_eq!(assert_ok!(label_to_ascii("Bücher", Options::default())), "xn--bcher-kva");
+    }
+
+    #[test]
+    fn std3_rules() {
+        assert_eq!(
+            assert_err!(label_to_ascii("a_b", Options::default())),
+            IdnaError::InvalidAsciiChar('_')
+        );
+        assert_eq!(
+            assert_err!(label_to_ascii("-bücher", Options::default())),
+            IdnaError::HyphenAtLabelBoundary
+        );
+        assert_ok!(label_to_ascii("a_b", NO_STD3));
+    }
+
+    #[test]
+    fn ace_prefixed_input() {
+        assert_eq!(
+            assert_err!(label_to_ascii("XN--bücher", NO_STD3)),
+            IdnaError::AcePrefixedInput
+        );
+    }
+
+    #[test]
+    fn label_too_long() {
+        let label = "ü".repeat(60);
+        assert_eq!(
+            assert_err!(label_to_ascii(&label, Options::default())),
+            IdnaError::LabelTooLong
+        );
+        // passes the early check but the encoded label is too long
+        let label: String = (0..59).map(|idx| char::from_u32(0x4e00 + idx * 97).unwrap()).collect();
+        assert_eq!(
+            assert_err!(label_to_ascii(&label, Options::default())),
+            IdnaError::LabelTooLong
+        );
+        assert_ok!(label_to_ascii(&"a".repeat(MAX_LABEL_LEN), Options::default()));
+        assert_eq!(
+            assert_err!(label_to_ascii(&"a".repeat(MAX_LABEL_LEN + 1), Options::default())),
+            IdnaError::LabelTooLong
+        );
+    }
+
+    #[test]
+    fn huge_label_is_rejected_before_encoding() {
+        let label: String = (0..100_000).filter_map(|idx| char::from_u32(0x100 + idx)).collect();
+        assert_eq!(
+            assert_err!(label_to_ascii(&label, Options::default())),
+            IdnaError::LabelTooLong
+        );
+    }
+
+    #[test]
+    fn label_to_unicode_roundtrip() {
+        let label = SoftAsciiStr::from_unchecked("xn--mnchen-3ya");
+        assert_eq!(assert_ok!(label_to_unicode(label, Options::default())), "münchen");
+        let label = SoftAsciiStr::from_unchecked("example");
+        assert_eq!(assert_ok!(label_to_unicode(label, Options::default())), "example");
+    }
+
+    #[test]
+    fn label_to_unicode_errors() {
+        let label = SoftAsciiStr::from_unchecked("xn--mnchen-3y");
+        assert_eq!(
+            assert_err!(label_to_unicode(label, Options::default())),
+            IdnaError::Punycode(PunycodeError::UnexpectedEnd)
+        );
+        // decodes to "abc" which is not encoded at all
+        let label = SoftAsciiStr::from_unchecked("xn--abc-");
+        assert_eq!(
+            assert_err!(label_to_unicode(label, Options::default())),
+            IdnaError::NotReversible
+        );
+    }
+
+    #[test]
+    fn huge_label_is_rejected_before_decoding() {
+        let label = format!("xn--{}", "a".repeat(100_000));
+        assert_eq!(
+            assert_err!(label_to_unicode(SoftAsciiStr::from_unchecked(&label), Options::default())),
+            IdnaError::LabelTooLong
+        );
+        let label = format!("xn--{}", "a".repeat(MAX_LABEL_LEN - 4));
+        assert_ok!(label_to_unicode(SoftAsciiStr::from_unchecked(&label), NO_STD3));
+    }
+
+    #[test]
+    fn domains() {
+        let ascii = assert_ok!(domain_to_ascii("www.bücher。example．", Options::default()));
+        assert_eq!(ascii, "www.xn--bcher-kva.example.");
+        let unicode = assert_ok!(domain_to_unicode(&ascii, Options::default()));
+        assert_eq!(unicode, "www.bücher.example.");
+    }
+
+    #[test]
+    fn empty_labels() {
+        assert_eq!(assert_err!(domain_to_ascii("a..b", Options::default())), IdnaError::EmptyLabel);
+        assert_eq!(assert_err!(domain_to_ascii("", Options::default())), IdnaError::EmptyLabel);
+        assert_eq!(
+            assert_err!(domain_to_unicode(SoftAsciiStr::from_unchecked(".a"), Options::default())),
+            IdnaError::EmptyLabel
+        );
+    }
+
+    #[test]
+    fn domain_too_long() {
+        let domain = vec!["abcdefghi"; 26].join(".");
+        assert_eq!(
+            assert_err!(domain_to_ascii(&domain, Options::default())),
+            IdnaError::DomainTooLong
+        );
+    }
+}
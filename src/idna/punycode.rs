@@ -0,0 +1,280 @@
+//! Punycode ([RFC 3492]) encoding and decoding
+//!
+//! [RFC 3492]: https://tools.ietf.org/html/rfc3492
+use core::char;
+use core::fmt::{self, Display};
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use soft_char::SoftAsciiChar;
+use soft_string::SoftAsciiString;
+
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+const DELIMITER: char = '-';
+
+/// Error returned if punycode encoding or decoding failed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PunycodeError {
+    /// the input is too long/contains too large code points to be represented
+    Overflow,
+    /// the char at the given byte offset is not a valid punycode digit
+    InvalidDigit(usize),
+    /// the input ended in the middle of a variable length integer
+    UnexpectedEnd,
+    /// the part before the last delimiter contains the non basic char at the given byte offset
+    NonBasicChar(usize),
+    /// the decoded code point is not a valid (non basic) char
+    InvalidCodePoint(u32)
+}
+
+impl Display for PunycodeError {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PunycodeError::Overflow =>
+                write!(fter, "punycode arithmetic overflow"),
+            PunycodeError::InvalidDigit(idx) =>
+                write!(fter, "invalid punycode digit at byte offset {}", idx),
+            PunycodeError::UnexpectedEnd =>
+                write!(fter, "punycode input ended unexpectedly"),
+            PunycodeError::NonBasicChar(idx) =>
+                write!(fter, "non basic char in punycode input at byte offset {}", idx),
+            PunycodeError::InvalidCodePoint(cp) =>
+                write!(fter, "punycode decoded invalid code point U+{:04X}", cp)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PunycodeError {
+    fn description(&self) -> &str {
+        "punycode encoding/decoding failed"
+    }
+}
+
+fn adapt(mut delta: u32, num_points: u32, first_time: bool) -> u32 {
+    delta /= if first_time { DAMP } else { 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (((BASE - T_MIN + 1) * delta) / (delta + SKEW))
+}
+
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        T_MIN
+    } else if k >= bias + T_MAX {
+        T_MAX
+    } else {
+        k - bias
+    }
+}
+
+fn encode_digit(digit: u32) -> SoftAsciiChar {
+    let byte = match digit {
+        0..=25 => b'a' + digit as u8,
+        _ => b'0' + (digit - 26) as u8
+    };
+    SoftAsciiChar::rewrap(byte as char)
+}
+
+fn decode_digit(ch: char) -> Option<u32> {
+    match ch {
+        'a'..='z' => Some(ch as u32 - 'a' as u32),
+        'A'..='Z' => Some(ch as u32 - 'A' as u32),
+        '0'..='9' => Some(ch as u32 - '0' as u32 + 26),
+        _ => None
+    }
+}
+
+/// encodes `input` using punycode
+///
+/// The output does not contain the IDNA `xn--` prefix, use
+/// `idna::label_to_ascii` for encoding domain labels.
+pub fn encode(input: &str) -> Result<SoftAsciiString, PunycodeError> {
+    let mut out = SoftAsciiString::with_capacity(input.len());
+    encode_into(input, &mut out)?;
+    Ok(out)
+}
+
+/// encodes `input` using punycode appending the result to `out`
+///
+/// If an error is returned `out` might have been partially written to.
+/// Encoding is quadratic in the worst case (one pass over the input per
+/// distinct non basic code point), so long untrusted input should be
+/// rejected beforehand, like `idna::label_to_ascii` does.
+pub fn encode_into(input: &str, out: &mut SoftAsciiString) -> Result<(), PunycodeError> {
+    let mut basic_count = 0u32;
+    let mut input_len = 0u32;
+    for ch in input.chars() {
+        input_len = input_len.checked_add(1).ok_or(PunycodeError::Overflow)?;
+        if ch.is_ascii() {
+            out.push(SoftAsciiChar::rewrap(ch));
+            basic_count += 1;
+        }
+    }
+    if basic_count > 0 {
+        out.push(SoftAsciiChar::rewrap(DELIMITER));
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta = 0u32;
+    let mut bias = INITIAL_BIAS;
+    let mut handled = basic_count;
+    while handled < input_len {
+        let min = input.chars()
+            .map(|ch| ch as u32)
+            .filter(|&cp| cp >= n)
+            .min()
+            .expect("[BUG] there has to be at least one unhandled code point");
+        delta = (min - n).checked_mul(handled + 1)
+            .and_then(|inc| delta.checked_add(inc))
+            .ok_or(PunycodeError::Overflow)?;
+        n = min;
+        for cp in input.chars().map(|ch| ch as u32) {
+            if cp < n {
+                delta = delta.checked_add(1).ok_or(PunycodeError::Overflow)?;
+            }
+            if cp == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    out.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                out.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic_count);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta = delta.checked_add(1).ok_or(PunycodeError::Overflow)?;
+        n += 1;
+    }
+    Ok(())
+}
+
+/// decodes the punycode encoded `input`
+///
+/// The input must not contain the IDNA `xn--` prefix, use
+/// `idna::label_to_unicode` for decoding domain labels.
+/// Punycode digits are case insensitive.
+pub fn decode(input: &str) -> Result<String, PunycodeError> {
+    let (basic, extended, extended_offset) = match input.rfind(DELIMITER) {
+        Some(idx) => (&input[..idx], &input[idx + 1..], idx + 1),
+        None => ("", input, 0)
+    };
+    if let Some(idx) = basic.bytes().position(|b| !b.is_ascii()) {
+        return Err(PunycodeError::NonBasicChar(idx));
+    }
+
+    let mut out: Vec<char> = basic.chars().collect();
+    let mut n = INITIAL_N;
+    let mut i = 0u32;
+    let mut bias = INITIAL_BIAS;
+    let mut digits = extended.char_indices().peekable();
+    while digits.peek().is_some() {
+        let old_i = i;
+        let mut weight = 1u32;
+        let mut k = BASE;
+        loop {
+            let (idx, ch) = digits.next().ok_or(PunycodeError::UnexpectedEnd)?;
+            let digit = decode_digit(ch)
+                .ok_or(PunycodeError::InvalidDigit(extended_offset + idx))?;
+            i = digit.checked_mul(weight)
+                .and_then(|inc| i.checked_add(inc))
+                .ok_or(PunycodeError::Overflow)?;
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            weight = weight.checked_mul(BASE - t).ok_or(PunycodeError::Overflow)?;
+            k += BASE;
+        }
+        let len = out.len() as u32 + 1;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len).ok_or(PunycodeError::Overflow)?;
+        i %= len;
+        let ch = match char::from_u32(n) {
+            Some(ch) if !ch.is_ascii() => ch,
+            _ => return Err(PunycodeError::InvalidCodePoint(n))
+        };
+        out.insert(i as usize, ch);
+        i += 1;
+    }
+    Ok(out.into_iter().collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{encode, decode, PunycodeError};
+
+    /// (decoded, encoded) samples, mostly from RFC 3492 section 7.1
+    const SAMPLES: &[(&str, &str)] = &[
+        ("ليهمابتكلموشعربي؟", "egbpdaj6bu4bxfgehfvwxn"),
+        ("他们为什么不说中文", "ihqwcrb4cv8a8dqg056pqjye"),
+        ("Pročprostěnemluvíčesky", "Proprostnemluvesky-uyb24dma41a"),
+        ("למההםפשוטלאמדבריםעברית", "4dbcagdahymbxekheh6e0a7fei0b"),
+        ("почемужеонинеговорятпорусски", "b1abfaaepdrnnbgefbadotcwatmq2g4l"),
+        ("3年B組金八先生", "3B-ww4c5e180e575a65lsy2b"),
+        ("安室奈美恵-with-SUPER-MONKEYS", "-with-SUPER-MONKEYS-pc58ag80a8qai00g7n9n"),
+        ("-> $1.00 <-", "-> $1.00 <--"),
+        ("bücher", "bcher-kva"),
+        ("münchen", "mnchen-3ya")
+    ];
+
+    #[test]
+    fn encode_samples() {
+        for &(decoded, encoded) in SAMPLES {
+            assert_eq!(assert_ok!(encode(decoded)), encoded);
+        }
+    }
+
+    #[test]
+    fn decode_samples() {
+        for &(decoded, encoded) in SAMPLES {
+            assert_eq!(assert_ok!(decode(encoded)), decoded);
+        }
+    }
+
+    #[test]
+    fn decode_is_case_insensitive() {
+        assert_eq!(assert_ok!(decode("BCHER-KVA")), "BüCHER");
+        assert_eq!(assert_ok!(decode("mnchen-3YA")), "münchen");
+    }
+
+    #[test]
+    fn decode_invalid_digit() {
+        assert_eq!(assert_err!(decode("abc-d!")), PunycodeError::InvalidDigit(5));
+    }
+
+    #[test]
+    fn decode_unexpected_end() {
+        assert_eq!(assert_err!(decode("mnchen-3y")), PunycodeError::UnexpectedEnd);
+    }
+
+    #[test]
+    fn decode_non_basic() {
+        assert_eq!(assert_err!(decode("mü-3ya")), PunycodeError::NonBasicChar(1));
+    }
+
+    #[test]
+    fn decode_overflow() {
+        assert_eq!(assert_err!(decode("99999999999")), PunycodeError::Overflow);
+    }
+}
@@ -0,0 +1,315 @@
+//! Interning of `SoftAsciiStr` values as compact `Symbol`s
+//!
+//! A `SoftAsciiInterner` maps each distinct value to a `Symbol` (a `u32`)
+//! and back. Interning an already known value and resolving a symbol
+//! don't allocate. Values are never removed, so resolved values live as
+//! long as the interner.
+//!
+//! In the case-insensitive mode values differing only in ascii case get
+//! the same symbol, which resolves to the spelling interned first.
+//!
+//! `SoftAsciiSyncInterner` is a thread-safe variant which can be shared
+//! between threads (e.g. through an `Arc` or a `LazyLock` static).
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::{SoftAsciiStr, SoftAsciiInterner};
+//!
+//! let mut interner = SoftAsciiInterner::case_insensitive();
+//! let content_type = interner.intern(SoftAsciiStr::from_unchecked("Content-Type"));
+//! let other = interner.intern(SoftAsciiStr::from_unchecked("content-type"));
+//! assert_eq!(content_type, other);
+//! assert_eq!(interner.resolve(other).unwrap(), "Content-Type");
+//! ```
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, RwLock, PoisonError};
+use std::vec::Vec;
+
+use soft_str::SoftAsciiStr;
+use uncased::SoftAsciiUncasedStr;
+
+/// a compact handle for a value interned in a `SoftAsciiInterner`
+///
+/// Symbols are only meaningful for the interner which created them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+
+    /// returns the index of the symbol (symbols are numbered in order of interning)
+    #[inline]
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+/// map key comparing and hashing the value ignoring ascii case
+struct UncasedKey(Arc<SoftAsciiStr>);
+
+impl Borrow<SoftAsciiUncasedStr> for UncasedKey {
+    #[inline]
+    fn borrow(&self) -> &SoftAsciiUncasedStr {
+        SoftAsciiUncasedStr::new(&self.0)
+    }
+}
+
+impl PartialEq for UncasedKey {
+    #[inline]
+    fn eq(&self, other: &UncasedKey) -> bool {
+        SoftAsciiUncasedStr::new(&self.0) == SoftAsciiUncasedStr::new(&other.0)
+    }
+}
+
+impl Eq for UncasedKey {}
+
+impl Hash for UncasedKey {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // has to be the same as for the borrowed form
+        SoftAsciiUncasedStr::new(&self.0).hash(state)
+    }
+}
+
+enum Symbols {
+    Cased(HashMap<Arc<SoftAsciiStr>, Symbol>),
+    Uncased(HashMap<UncasedKey, Symbol>)
+}
+
+/// maps `SoftAsciiStr` values to `Symbol`s and back
+pub struct SoftAsciiInterner {
+    symbols: Symbols,
+    // indexed by `Symbol::as_u32`
+    values: Vec<Arc<SoftAsciiStr>>
+}
+
+impl SoftAsciiInterner {
+
+    /// creates an interner which is case sensitive
+    pub fn new() -> Self {
+        SoftAsciiInterner {
+            symbols: Symbols::Cased(HashMap::new()),
+            values: Vec::new()
+        }
+    }
+
+    /// creates an interner which ignores ascii case
+    pub fn case_insensitive() -> Self {
+        SoftAsciiInterner {
+            symbols: Symbols::Uncased(HashMap::new()),
+            values: Vec::new()
+        }
+    }
+
+    pub fn is_case_insensitive(&self) -> bool {
+        match self.symbols {
+            Symbols::Cased(_) => false,
+            Symbols::Uncased(_) => true
+        }
+    }
+
+    /// returns the number of interned values
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// returns the symbol for `value` interning it if it's not known yet
+    ///
+    /// # Panics
+    ///
+    /// if more than `u32::MAX` values are interned
+    pub fn intern(&mut self, value: &SoftAsciiStr) -> Symbol {
+        if let Some(symbol) = self.get(value) {
+            return symbol;
+        }
+        let symbol = Symbol(u32::try_from(self.values.len())
+            .expect("more than u32::MAX values interned"));
+        let value: Arc<SoftAsciiStr> = Arc::from(value);
+        // pushed first, so that a symbol in the map always resolves
+        self.values.push(value.clone());
+        match self.symbols {
+            Symbols::Cased(ref mut map) => { map.insert(value, symbol); },
+            Symbols::Uncased(ref mut map) => { map.insert(UncasedKey(value), symbol); }
+        }
+        symbol
+    }
+
+    /// returns the symbol for `value` if it was interned
+    pub fn get(&self, value: &SoftAsciiStr) -> Option<Symbol> {
+        match self.symbols {
+            Symbols::Cased(ref map) => map.get(value),
+            Symbols::Uncased(ref map) => map.get(SoftAsciiUncasedStr::new(value))
+        }.cloned()
+    }
+
+    /// returns the value for `symbol`, or `None` if it's not from this interner
+    #[inline]
+    pub fn resolve(&self, symbol: Symbol) -> Option<&SoftAsciiStr> {
+        self.values.get(symbol.0 as usize).map(|value| &**value)
+    }
+}
+
+impl Default for SoftAsciiInterner {
+    fn default() -> Self {
+        SoftAsciiInterner::new()
+    }
+}
+
+/// a thread-safe `SoftAsciiInterner`
+///
+/// Lookups of known values only take a read lock.
+pub struct SoftAsciiSyncInterner {
+    inner: RwLock<SoftAsciiInterner>
+}
+
+impl SoftAsciiSyncInterner {
+
+    /// creates an interner which is case sensitive
+    pub fn new() -> Self {
+        SoftAsciiSyncInterner { inner: RwLock::new(SoftAsciiInterner::new()) }
+    }
+
+    /// creates an interner which ignores ascii case
+    pub fn case_insensitive() -> Self {
+        SoftAsciiSyncInterner { inner: RwLock::new(SoftAsciiInterner::case_insensitive()) }
+    }
+
+    pub fn is_case_insensitive(&self) -> bool {
+        self.read(|interner| interner.is_case_insensitive())
+    }
+
+    /// returns the number of interned values
+    pub fn len(&self) -> usize {
+        self.read(|interner| interner.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read(|interner| interner.is_empty())
+    }
+
+    /// returns the symbol for `value` interning it if it's not known yet
+    ///
+    /// # Panics
+    ///
+    /// if more than `u32::MAX` values are interned
+    pub fn intern(&self, value: &SoftAsciiStr) -> Symbol {
+        if let Some(symbol) = self.get(value) {
+            return symbol;
+        }
+        // `intern` re-checks, another thread might have interned it in between
+        self.inner.write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .intern(value)
+    }
+
+    /// returns the symbol for `value` if it was interned
+    pub fn get(&self, value: &SoftAsciiStr) -> Option<Symbol> {
+        self.read(|interner| interner.get(value))
+    }
+
+    /// returns the value for `symbol`, or `None` if it's not from this interner
+    pub fn resolve(&self, symbol: Symbol) -> Option<&SoftAsciiStr> {
+        self.read(|interner| {
+            // Safe: values are never removed and the `Arc`s keep their
+            // (heap allocated) value at the same address, so it lives as
+            // long as `self`, even if the lock is released
+            interner.values.get(symbol.0 as usize)
+                .map(|value| unsafe { &*Arc::as_ptr(value) })
+        })
+    }
+
+    /// returns the (not thread-safe) interner
+    pub fn into_inner(self) -> SoftAsciiInterner {
+        self.inner.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn read<R, F: FnOnce(&SoftAsciiInterner) -> R>(&self, func: F) -> R {
+        // the interner is consistent after each operation, so poisoning can be ignored
+        func(&self.inner.read().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl Default for SoftAsciiSyncInterner {
+    fn default() -> Self {
+        SoftAsciiSyncInterner::new()
+    }
+}
+
+impl From<SoftAsciiInterner> for SoftAsciiSyncInterner {
+    fn from(interner: SoftAsciiInterner) -> Self {
+        SoftAsciiSyncInterner { inner: RwLock::new(interner) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::thread;
+    use std::vec::Vec;
+    use macros::soft;
+    use super::{SoftAsciiInterner, SoftAsciiSyncInterner, Symbol};
+
+    #[test]
+    fn intern_and_resolve() {
+        let mut interner = SoftAsciiInterner::new();
+        assert!(interner.is_empty());
+        let to = interner.intern(soft("To"));
+        let from = interner.intern(soft("From"));
+        assert_ne!(to, from);
+        assert_eq!(interner.intern(soft("To")), to);
+        assert_eq!(interner.intern(soft("to")).as_u32(), 2);
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.get(soft("From")), Some(from));
+        assert_eq!(interner.get(soft("Cc")), None);
+        assert_eq!(interner.resolve(to).unwrap(), "To");
+        assert_eq!(interner.resolve(Symbol(3)), None);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let mut interner = SoftAsciiInterner::case_insensitive();
+        assert!(interner.is_case_insensitive());
+        let first = interner.intern(soft("Content-Type"));
+        assert_eq!(interner.intern(soft("CONTENT-TYPE")), first);
+        assert_eq!(interner.get(soft("content-type")), Some(first));
+        assert_ne!(interner.intern(soft("Content_Type")), first);
+        assert_eq!(interner.resolve(first).unwrap(), "Content-Type");
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn sync_interner_resolves_across_threads() {
+        let interner = Arc::new(SoftAsciiSyncInterner::case_insensitive());
+        let names = ["To", "From", "Subject", "Date"];
+        let handles: Vec<_> = (0..4).map(|idx| {
+            let interner = interner.clone();
+            thread::spawn(move || {
+                names.iter().cycle().skip(idx).take(names.len())
+                    .map(|name| interner.intern(soft(name)))
+                    .collect::<Vec<Symbol>>()
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(interner.len(), names.len());
+        let subject = interner.intern(soft("SUBJECT"));
+        let resolved = interner.resolve(subject).unwrap();
+        // more interning doesn't invalidate resolved values
+        for idx in 0..100 {
+            interner.intern(soft(&format!("X-Header-{}", idx)));
+        }
+        assert_eq!(resolved, "Subject");
+        let interner = Arc::try_unwrap(interner).ok().unwrap().into_inner();
+        assert_eq!(interner.len(), names.len() + 100);
+    }
+}
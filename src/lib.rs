@@ -16,22 +16,100 @@
 //! This crate is not necessarily suited if you want to rally on the string
 //! being ascii on a safety level, you might want to consider using
 //! [ascii](https://crates.io/crates/ascii) in that case.
+//!
+//! # Features
+//!
+//! - `std` (default): implementations for `std` only types/traits
+//!   like `OsStr`, `Path`, `ToSocketAddrs` and `std::error::Error`
+//!   and the `interner` module (`SoftAsciiInterner`, `SoftAsciiSyncInterner`), implies `alloc`
+//! - `alloc`: `SoftAsciiString` and all functionality which needs
+//!   to allocate (e.g. `Box<SoftAsciiStr>`, `to_owned`)
+//! - `serde`: `Serialize`/`Deserialize` implementations, see `serde_support`
+//! - `check-unchecked`: re-check the soft constraint in all `from_unchecked`
+//!   constructors and when the `inner_*_mut_checked` guards are dropped, see `check`
+//! - `track-unchecked`: record where unchecked `SoftAsciiStr`/`SoftAsciiString`
+//!   values were created for debugging, see `track`, implies `std`
+//!   (`SoftAsciiChar` values are not tracked)
+//! - `nightly`: implement `std::str::pattern::Pattern` for `SoftAsciiChar`
+//!   and `&SoftAsciiStr` (requires a nightly compiler)
+//!
+//! Without the `std` feature this crate is `#![no_std]`, `SoftAsciiChar`
+//! and `SoftAsciiStr` only need `core`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "nightly", feature(pattern))]
+
+#[cfg(any(feature = "std", test))]
+extern crate core;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_test;
 
 pub use self::soft_char::*;
 pub use self::soft_str::*;
+#[cfg(feature = "alloc")]
 pub use self::soft_string::*;
+pub use self::uncased::*;
+pub use self::array_string::*;
+#[cfg(feature = "alloc")]
+pub use self::small_string::*;
+#[cfg(feature = "alloc")]
+pub use self::shared::*;
+#[cfg(feature = "std")]
+pub use self::interner::{SoftAsciiInterner, SoftAsciiSyncInterner, Symbol};
 
 #[macro_use]
 mod macros;
 
 pub mod error;
+#[cfg(feature = "check-unchecked")]
+pub mod check;
+#[cfg(feature = "track-unchecked")]
+pub mod track;
+// without the feature it's only used for `ViolationInfo::created_at` in debug builds
+#[cfg(all(not(feature = "track-unchecked"), feature = "std", debug_assertions))]
+#[allow(dead_code)]
+mod track;
+pub mod violation;
+mod validate;
+mod case;
 mod soft_char;
 // note while they are separated for readability str/string
 // still do form one unit, i.e. there is a cyclic reference
 // between SoftAsciiString<->SoftAsciiStr and others
 mod soft_str;
+#[cfg(feature = "alloc")]
 mod soft_string;
+mod uncased;
+mod array_string;
+#[cfg(feature = "alloc")]
+mod small_string;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "std")]
+pub mod interner;
+pub mod pattern;
+#[cfg(feature = "nightly")]
+mod nightly;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+#[cfg(feature = "alloc")]
+pub mod transliterate;
+#[cfg(feature = "alloc")]
+pub mod idna;
+#[cfg(feature = "alloc")]
+pub mod percent;
+#[cfg(feature = "alloc")]
+pub mod encoded_word;
+#[cfg(feature = "alloc")]
+pub mod quoted_printable;
+#[cfg(feature = "alloc")]
+pub mod base64;
+#[cfg(feature = "alloc")]
+pub mod hex;
 
 
 // - IndexMut (returns a &mut SoftAsciiStr)
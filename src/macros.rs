@@ -1,3 +1,170 @@
+/// creates a `&'static SoftAsciiStr` from a literal, checked at compile time
+///
+/// It can be used in `const`/`static` items, non us-ascii input is a
+/// compile error (see `SoftAsciiStr::from_str_const`).
+///
+/// ```
+/// #[macro_use]
+/// extern crate soft_ascii_string;
+/// use soft_ascii_string::SoftAsciiStr;
+///
+/// const CONTENT_TYPE: &SoftAsciiStr = soft_ascii!("Content-Type");
+///
+/// fn main() {
+///     assert_eq!(CONTENT_TYPE, "Content-Type");
+///     assert_eq!(soft_ascii!(concat!("utf", "-8")), "utf-8");
+/// }
+/// ```
+///
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate soft_ascii_string;
+///
+/// fn main() {
+///     let _ = soft_ascii!("↓");
+/// }
+/// ```
+#[macro_export]
+macro_rules! soft_ascii {
+    ($s:expr) => ({
+        const VALUE: &'static $crate::SoftAsciiStr = $crate::SoftAsciiStr::from_str_const($s);
+        VALUE
+    });
+}
+
+/// creates a `SoftAsciiChar` from a literal, checked at compile time
+///
+/// It can be used in `const`/`static` items, a non us-ascii char is a
+/// compile error (see `SoftAsciiChar::from_char_const`).
+///
+/// ```
+/// #[macro_use]
+/// extern crate soft_ascii_string;
+/// use soft_ascii_string::SoftAsciiChar;
+///
+/// static SEPARATOR: SoftAsciiChar = soft_ascii_char!(':');
+///
+/// fn main() {
+///     assert_eq!(SEPARATOR, ':');
+/// }
+/// ```
+///
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate soft_ascii_string;
+///
+/// fn main() {
+///     let _ = soft_ascii_char!('↓');
+/// }
+/// ```
+#[macro_export]
+macro_rules! soft_ascii_char {
+    ($ch:expr) => ({
+        const VALUE: $crate::SoftAsciiChar = $crate::SoftAsciiChar::from_char_const($ch);
+        VALUE
+    });
+}
+
+/// implements `AsRef`, `Borrow`, `PartialEq`, `Eq`, `PartialOrd`, `Ord`, `Hash`,
+/// `Debug` and `Display` for a type which derefs to `SoftAsciiStr`
+///
+/// The type needs `as_soft_ascii_str` and `as_str` methods, all impls
+/// behave like the ones of the `SoftAsciiStr` it derefs to.
+macro_rules! impl_soft_ascii_str_traits {
+    ([$($gen:tt)*] $tp:ty) => (
+        impl<$($gen)*> AsRef<$crate::SoftAsciiStr> for $tp {
+            #[inline]
+            fn as_ref(&self) -> &$crate::SoftAsciiStr {
+                self
+            }
+        }
+
+        impl<$($gen)*> AsRef<str> for $tp {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl<$($gen)*> ::core::borrow::Borrow<$crate::SoftAsciiStr> for $tp {
+            #[inline]
+            fn borrow(&self) -> &$crate::SoftAsciiStr {
+                self
+            }
+        }
+
+        impl<$($gen)*> PartialEq for $tp {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.as_soft_ascii_str() == other.as_soft_ascii_str()
+            }
+        }
+
+        impl<$($gen)*> Eq for $tp {}
+
+        impl<$($gen)*> PartialEq<$crate::SoftAsciiStr> for $tp {
+            #[inline]
+            fn eq(&self, other: &$crate::SoftAsciiStr) -> bool {
+                self.as_soft_ascii_str() == other
+            }
+        }
+
+        impl<'a, $($gen)*> PartialEq<&'a $crate::SoftAsciiStr> for $tp {
+            #[inline]
+            fn eq(&self, other: &&'a $crate::SoftAsciiStr) -> bool {
+                self.as_soft_ascii_str() == *other
+            }
+        }
+
+        impl<$($gen)*> PartialEq<str> for $tp {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl<'a, $($gen)*> PartialEq<&'a str> for $tp {
+            #[inline]
+            fn eq(&self, other: &&'a str) -> bool {
+                self.as_str() == *other
+            }
+        }
+
+        impl<$($gen)*> PartialOrd for $tp {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<$($gen)*> Ord for $tp {
+            #[inline]
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                self.as_soft_ascii_str().cmp(other.as_soft_ascii_str())
+            }
+        }
+
+        impl<$($gen)*> ::core::hash::Hash for $tp {
+            #[inline]
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                // has to be the same as for `SoftAsciiStr` (see `Borrow`)
+                ::core::hash::Hash::hash(self.as_soft_ascii_str(), state)
+            }
+        }
+
+        impl<$($gen)*> ::core::fmt::Debug for $tp {
+            fn fmt(&self, fter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(self.as_str(), fter)
+            }
+        }
+
+        impl<$($gen)*> ::core::fmt::Display for $tp {
+            fn fmt(&self, fter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(self.as_str(), fter)
+            }
+        }
+    );
+}
 
 #[doc(hidden)]
 #[cfg(test)]
@@ -20,3 +187,9 @@ macro_rules! assert_err {
         }
     });
 }
+
+/// wraps `s` without any checks, for tests of functions taking a `&SoftAsciiStr`
+#[cfg(test)]
+pub(crate) fn soft(s: &str) -> &::SoftAsciiStr {
+    ::SoftAsciiStr::from_unchecked(s)
+}
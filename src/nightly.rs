@@ -0,0 +1,118 @@
+//! implementations of unstable std traits, enabled with the `nightly` feature
+//!
+//! `SoftAsciiChar` and `&SoftAsciiStr` implement `std::str::pattern::Pattern`
+//! so they can be used with the search methods of `str` (e.g.
+//! `"a,b".split(soft_char)`) by delegating to `char`/`&str`.
+//!
+//! `SliceIndex<SoftAsciiStr>` is not implemented as `SliceIndex` can only
+//! be implemented inside of `core`, indexing keeps using `Index` and
+//! `get_unchecked` (see `hidden::TempSliceIndexHelper`).
+use core::str::pattern::{Pattern, CharSearcher, StrSearcher, ReverseSearcher};
+
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+
+impl Pattern for SoftAsciiChar {
+    type Searcher<'a> = CharSearcher<'a>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> CharSearcher<'_> {
+        char::from(self).into_searcher(haystack)
+    }
+
+    #[inline]
+    fn is_contained_in(self, haystack: &str) -> bool {
+        char::from(self).is_contained_in(haystack)
+    }
+
+    #[inline]
+    fn is_prefix_of(self, haystack: &str) -> bool {
+        char::from(self).is_prefix_of(haystack)
+    }
+
+    #[inline]
+    fn is_suffix_of<'a>(self, haystack: &'a str) -> bool
+        where CharSearcher<'a>: ReverseSearcher<'a>
+    {
+        char::from(self).is_suffix_of(haystack)
+    }
+
+    #[inline]
+    fn strip_prefix_of(self, haystack: &str) -> Option<&str> {
+        char::from(self).strip_prefix_of(haystack)
+    }
+
+    #[inline]
+    fn strip_suffix_of<'a>(self, haystack: &'a str) -> Option<&'a str>
+        where CharSearcher<'a>: ReverseSearcher<'a>
+    {
+        char::from(self).strip_suffix_of(haystack)
+    }
+}
+
+impl<'b> Pattern for &'b SoftAsciiStr {
+    type Searcher<'a> = StrSearcher<'a, 'b>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> StrSearcher<'_, 'b> {
+        self.as_str().into_searcher(haystack)
+    }
+
+    #[inline]
+    fn is_contained_in(self, haystack: &str) -> bool {
+        self.as_str().is_contained_in(haystack)
+    }
+
+    #[inline]
+    fn is_prefix_of(self, haystack: &str) -> bool {
+        self.as_str().is_prefix_of(haystack)
+    }
+
+    #[inline]
+    fn is_suffix_of<'a>(self, haystack: &'a str) -> bool
+        where StrSearcher<'a, 'b>: ReverseSearcher<'a>
+    {
+        self.as_str().is_suffix_of(haystack)
+    }
+
+    #[inline]
+    fn strip_prefix_of(self, haystack: &str) -> Option<&str> {
+        self.as_str().strip_prefix_of(haystack)
+    }
+
+    #[inline]
+    fn strip_suffix_of<'a>(self, haystack: &'a str) -> Option<&'a str>
+        where StrSearcher<'a, 'b>: ReverseSearcher<'a>
+    {
+        self.as_str().strip_suffix_of(haystack)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+    use soft_char::SoftAsciiChar;
+    use soft_str::SoftAsciiStr;
+
+    #[test]
+    fn soft_ascii_char_pattern() {
+        let comma = SoftAsciiChar::from_unchecked(',');
+        assert_eq!("a,b,c".split(comma).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!("a,b,c".rsplit(comma).collect::<Vec<_>>(), vec!["c", "b", "a"]);
+        assert_eq!(",a,".trim_matches(comma), "a");
+        assert_eq!("a,b".find(comma), Some(1));
+        assert!(",a".starts_with(comma));
+        assert_eq!("a,".strip_suffix(comma), Some("a"));
+    }
+
+    #[test]
+    fn soft_ascii_str_pattern() {
+        let sep = SoftAsciiStr::from_unchecked(", ");
+        assert_eq!("a, b, c".split(sep).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!("a, b, c".rfind(sep), Some(4));
+        assert!("a, b".contains(sep));
+        assert_eq!(", a".strip_prefix(sep), Some("a"));
+        assert!("a, ".ends_with(sep));
+        assert_eq!("a, b, c".replace(sep, ";"), "a;b;c");
+    }
+}
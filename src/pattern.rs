@@ -0,0 +1,557 @@
+//! A stable pattern API for searching and splitting `SoftAsciiStr`s
+//!
+//! `std::str::pattern::Pattern` can't be implemented (or used as bound) on
+//! stable rust, so the search and split methods of `SoftAsciiStr` use the
+//! crate local [`Pattern`](trait.Pattern.html) trait instead. It's implemented
+//! for `SoftAsciiChar`, `&SoftAsciiStr`, `char`, `&str`, `&[char]`, `[char; N]`,
+//! `&[char; N]`, `&[SoftAsciiChar]` and `FnMut(SoftAsciiChar) -> bool`.
+//!
+//! All results are `&SoftAsciiStr` (or iterators of them) so there is no
+//! need to re-wrap the results of `.as_str().split(..)` anymore.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::{SoftAsciiChar, SoftAsciiStr};
+//!
+//! let soft = SoftAsciiStr::from_str("key=value; other=thing").unwrap();
+//! let mut pairs = soft.split("; ");
+//! let (key, value) = pairs.next().unwrap().split_once('=').unwrap();
+//! assert_eq!(key, "key");
+//! assert_eq!(value, "value");
+//!
+//! let is_digit = |ch: SoftAsciiChar| ch.is_digit(10);
+//! assert_eq!(SoftAsciiStr::from_str("007abc").unwrap().trim_matches(is_digit), "abc");
+//! ```
+use core::fmt;
+
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+
+/// a pattern which can be searched for in a `SoftAsciiStr`
+///
+/// All byte offsets passed to and returned from the methods have to be
+/// on char boundaries. A pattern may match the empty string (e.g. `""`),
+/// the iterators then advance by one char after each empty match like
+/// the ones of `str` do.
+pub trait Pattern {
+    /// returns the byte range of the first match in `haystack`
+    fn find_in(&mut self, haystack: &str) -> Option<(usize, usize)>;
+
+    /// returns the byte range of the last match in `haystack`
+    fn rfind_in(&mut self, haystack: &str) -> Option<(usize, usize)>;
+
+    /// returns the length of the match at the start of `haystack`, if there is one
+    fn prefix_len_in(&mut self, haystack: &str) -> Option<usize>;
+
+    /// returns the length of the match at the end of `haystack`, if there is one
+    fn suffix_len_in(&mut self, haystack: &str) -> Option<usize>;
+}
+
+/// returns the length of the char starting at `idx`
+fn char_len_at(haystack: &str, idx: usize) -> usize {
+    haystack[idx..].chars().next()
+        .expect("[BUG] match has to point to a char")
+        .len_utf8()
+}
+
+/// implements `Pattern` for a type matching single chars which std can search for
+macro_rules! impl_char_pattern {
+    ($([$($gen:tt)*] $tp:ty => |$this:ident| $pat:expr),*) => ($(
+        impl<$($gen)*> Pattern for $tp {
+            fn find_in(&mut self, haystack: &str) -> Option<(usize, usize)> {
+                let $this = self;
+                haystack.find($pat)
+                    .map(|idx| (idx, idx + char_len_at(haystack, idx)))
+            }
+
+            fn rfind_in(&mut self, haystack: &str) -> Option<(usize, usize)> {
+                let $this = self;
+                haystack.rfind($pat)
+                    .map(|idx| (idx, idx + char_len_at(haystack, idx)))
+            }
+
+            fn prefix_len_in(&mut self, haystack: &str) -> Option<usize> {
+                let $this = self;
+                if haystack.starts_with($pat) {
+                    Some(char_len_at(haystack, 0))
+                } else {
+                    None
+                }
+            }
+
+            fn suffix_len_in(&mut self, haystack: &str) -> Option<usize> {
+                let $this = self;
+                if haystack.ends_with($pat) {
+                    haystack.chars().next_back().map(char::len_utf8)
+                } else {
+                    None
+                }
+            }
+        }
+    )*);
+}
+
+impl_char_pattern! {
+    [] char => |this| *this,
+    [] SoftAsciiChar => |this| char::from(*this),
+    ['b] &'b [char] => |this| *this,
+    [const N: usize] [char; N] => |this| &this[..],
+    ['b, const N: usize] &'b [char; N] => |this| &this[..],
+    ['b] &'b [SoftAsciiChar] => |this| |ch: char| this.iter().any(|&soft| soft == ch),
+    [F: FnMut(SoftAsciiChar) -> bool] F => |this| |ch: char| this(SoftAsciiChar::rewrap(ch))
+}
+
+impl Pattern for &str {
+    fn find_in(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        haystack.find(*self).map(|idx| (idx, idx + self.len()))
+    }
+
+    fn rfind_in(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        haystack.rfind(*self).map(|idx| (idx, idx + self.len()))
+    }
+
+    fn prefix_len_in(&mut self, haystack: &str) -> Option<usize> {
+        if haystack.starts_with(*self) { Some(self.len()) } else { None }
+    }
+
+    fn suffix_len_in(&mut self, haystack: &str) -> Option<usize> {
+        if haystack.ends_with(*self) { Some(self.len()) } else { None }
+    }
+}
+
+impl Pattern for &SoftAsciiStr {
+    fn find_in(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        self.as_str().find_in(haystack)
+    }
+
+    fn rfind_in(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        self.as_str().rfind_in(haystack)
+    }
+
+    fn prefix_len_in(&mut self, haystack: &str) -> Option<usize> {
+        self.as_str().prefix_len_in(haystack)
+    }
+
+    fn suffix_len_in(&mut self, haystack: &str) -> Option<usize> {
+        self.as_str().suffix_len_in(haystack)
+    }
+}
+
+/// shared state of the forward iterators
+#[derive(Clone)]
+struct Forward<'a, P> {
+    haystack: &'a str,
+    pat: P,
+    /// where the search for the next match starts, `None` if there are no more matches
+    position: Option<usize>
+}
+
+impl<'a, P: Pattern> Forward<'a, P> {
+
+    fn new(haystack: &'a str, pat: P) -> Self {
+        Forward { haystack, pat, position: Some(0) }
+    }
+
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let position = self.position?;
+        match self.pat.find_in(&self.haystack[position..]) {
+            None => {
+                self.position = None;
+                None
+            },
+            Some((start, end)) => {
+                let (start, end) = (position + start, position + end);
+                self.position = if start < end {
+                    Some(end)
+                } else if end < self.haystack.len() {
+                    // don't find the same empty match again
+                    Some(end + char_len_at(self.haystack, end))
+                } else {
+                    None
+                };
+                Some((start, end))
+            }
+        }
+    }
+}
+
+/// shared state of the backward iterators
+#[derive(Clone)]
+struct Backward<'a, P> {
+    haystack: &'a str,
+    pat: P,
+    /// where the search for the previous match ends, `None` if there are no more matches
+    position: Option<usize>
+}
+
+impl<'a, P: Pattern> Backward<'a, P> {
+
+    fn new(haystack: &'a str, pat: P) -> Self {
+        Backward { haystack, pat, position: Some(haystack.len()) }
+    }
+
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let position = self.position?;
+        match self.pat.rfind_in(&self.haystack[..position]) {
+            None => {
+                self.position = None;
+                None
+            },
+            Some((start, end)) => {
+                self.position = if start < end {
+                    Some(start)
+                } else if start > 0 {
+                    // don't find the same empty match again
+                    self.haystack[..start].chars().next_back()
+                        .map(|ch| start - ch.len_utf8())
+                } else {
+                    None
+                };
+                Some((start, end))
+            }
+        }
+    }
+}
+
+macro_rules! impl_debug {
+    ($($name:ident),*) => ($(
+        impl<'a, P> fmt::Debug for $name<'a, P> {
+            fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+                fter.debug_struct(stringify!($name))
+                    .field("haystack", &self.inner.haystack)
+                    .finish()
+            }
+        }
+    )*);
+}
+
+/// an iterator over the parts of a `SoftAsciiStr` separated by a pattern
+///
+/// This iterator is returned by `SoftAsciiStr::split`.
+#[derive(Clone)]
+pub struct SoftAsciiSplit<'a, P> {
+    inner: Forward<'a, P>,
+    start: usize,
+    finished: bool
+}
+
+impl<'a, P: Pattern> SoftAsciiSplit<'a, P> {
+    pub(crate) fn new(haystack: &'a SoftAsciiStr, pat: P) -> Self {
+        SoftAsciiSplit {
+            inner: Forward::new(haystack.as_str(), pat),
+            start: 0,
+            finished: false
+        }
+    }
+
+    fn remainder(&mut self) -> Option<&'a SoftAsciiStr> {
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        Some(SoftAsciiStr::rewrap(&self.inner.haystack[self.start..]))
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SoftAsciiSplit<'a, P> {
+    type Item = &'a SoftAsciiStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.inner.next_match() {
+            Some((start, end)) => {
+                let part = &self.inner.haystack[self.start..start];
+                self.start = end;
+                Some(SoftAsciiStr::rewrap(part))
+            },
+            None => self.remainder()
+        }
+    }
+}
+
+/// an iterator over the parts of a `SoftAsciiStr` separated by a pattern in reverse order
+///
+/// This iterator is returned by `SoftAsciiStr::rsplit`.
+#[derive(Clone)]
+pub struct SoftAsciiRSplit<'a, P> {
+    inner: Backward<'a, P>,
+    end: usize,
+    finished: bool
+}
+
+impl<'a, P: Pattern> SoftAsciiRSplit<'a, P> {
+    pub(crate) fn new(haystack: &'a SoftAsciiStr, pat: P) -> Self {
+        SoftAsciiRSplit {
+            inner: Backward::new(haystack.as_str(), pat),
+            end: haystack.len(),
+            finished: false
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SoftAsciiRSplit<'a, P> {
+    type Item = &'a SoftAsciiStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.inner.next_match() {
+            Some((start, end)) => {
+                let part = &self.inner.haystack[end..self.end];
+                self.end = start;
+                Some(SoftAsciiStr::rewrap(part))
+            },
+            None => {
+                self.finished = true;
+                Some(SoftAsciiStr::rewrap(&self.inner.haystack[..self.end]))
+            }
+        }
+    }
+}
+
+/// an iterator over at most `n` parts of a `SoftAsciiStr` separated by a pattern
+///
+/// This iterator is returned by `SoftAsciiStr::splitn`, the last part
+/// contains the remainder of the string.
+#[derive(Clone)]
+pub struct SoftAsciiSplitN<'a, P> {
+    inner: SoftAsciiSplit<'a, P>,
+    count: usize
+}
+
+impl<'a, P: Pattern> SoftAsciiSplitN<'a, P> {
+    pub(crate) fn new(haystack: &'a SoftAsciiStr, count: usize, pat: P) -> Self {
+        SoftAsciiSplitN {
+            inner: SoftAsciiSplit::new(haystack, pat),
+            count
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SoftAsciiSplitN<'a, P> {
+    type Item = &'a SoftAsciiStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.inner.remainder()
+            },
+            _ => {
+                self.count -= 1;
+                self.inner.next()
+            }
+        }
+    }
+}
+
+/// like `SoftAsciiSplit` but a trailing empty part is skipped
+///
+/// This iterator is returned by `SoftAsciiStr::split_terminator`.
+#[derive(Clone)]
+pub struct SoftAsciiSplitTerminator<'a, P> {
+    inner: SoftAsciiSplit<'a, P>
+}
+
+impl<'a, P: Pattern> SoftAsciiSplitTerminator<'a, P> {
+    pub(crate) fn new(haystack: &'a SoftAsciiStr, pat: P) -> Self {
+        SoftAsciiSplitTerminator {
+            inner: SoftAsciiSplit::new(haystack, pat)
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SoftAsciiSplitTerminator<'a, P> {
+    type Item = &'a SoftAsciiStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let was_finished = self.inner.finished;
+        let part = self.inner.next()?;
+        if part.is_empty() && !was_finished && self.inner.finished {
+            None
+        } else {
+            Some(part)
+        }
+    }
+}
+
+/// an iterator over the matches of a pattern in a `SoftAsciiStr`
+///
+/// This iterator is returned by `SoftAsciiStr::matches`.
+#[derive(Clone)]
+pub struct SoftAsciiMatches<'a, P> {
+    inner: Forward<'a, P>
+}
+
+impl<'a, P: Pattern> SoftAsciiMatches<'a, P> {
+    pub(crate) fn new(haystack: &'a SoftAsciiStr, pat: P) -> Self {
+        SoftAsciiMatches { inner: Forward::new(haystack.as_str(), pat) }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SoftAsciiMatches<'a, P> {
+    type Item = &'a SoftAsciiStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_match()
+            .map(|(start, end)| SoftAsciiStr::rewrap(&self.inner.haystack[start..end]))
+    }
+}
+
+/// an iterator over the matches of a pattern in a `SoftAsciiStr` and their byte offsets
+///
+/// This iterator is returned by `SoftAsciiStr::match_indices`.
+#[derive(Clone)]
+pub struct SoftAsciiMatchIndices<'a, P> {
+    inner: Forward<'a, P>
+}
+
+impl<'a, P: Pattern> SoftAsciiMatchIndices<'a, P> {
+    pub(crate) fn new(haystack: &'a SoftAsciiStr, pat: P) -> Self {
+        SoftAsciiMatchIndices { inner: Forward::new(haystack.as_str(), pat) }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SoftAsciiMatchIndices<'a, P> {
+    type Item = (usize, &'a SoftAsciiStr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_match()
+            .map(|(start, end)| (start, SoftAsciiStr::rewrap(&self.inner.haystack[start..end])))
+    }
+}
+
+impl<'a, P> fmt::Debug for SoftAsciiSplitN<'a, P> {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        fter.debug_struct("SoftAsciiSplitN")
+            .field("haystack", &self.inner.inner.haystack)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+impl<'a, P> fmt::Debug for SoftAsciiSplitTerminator<'a, P> {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        fter.debug_struct("SoftAsciiSplitTerminator")
+            .field("haystack", &self.inner.inner.haystack)
+            .finish()
+    }
+}
+
+impl_debug!(SoftAsciiSplit, SoftAsciiRSplit, SoftAsciiMatches, SoftAsciiMatchIndices);
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+    use macros::soft;
+    use soft_char::SoftAsciiChar;
+    use soft_str::SoftAsciiStr;
+
+    fn strs<'a, I: Iterator<Item=&'a SoftAsciiStr>>(iter: I) -> Vec<&'a str> {
+        iter.map(SoftAsciiStr::as_str).collect()
+    }
+
+    const HAYSTACKS: &[&str] = &[
+        "", "a", "ab", "a,b", ",a,,b,", "aaa", "abcabc", ",,", "a, b, c", "  x  "
+    ];
+
+    macro_rules! assert_same_as_str {
+        ($pat:expr) => ({
+            for &haystack in HAYSTACKS {
+                let sa = soft(haystack);
+                assert_eq!(strs(sa.split($pat)), haystack.split($pat).collect::<Vec<_>>(),
+                    "split {:?}", haystack);
+                assert_eq!(strs(sa.rsplit($pat)), haystack.rsplit($pat).collect::<Vec<_>>(),
+                    "rsplit {:?}", haystack);
+                assert_eq!(strs(sa.split_terminator($pat)),
+                    haystack.split_terminator($pat).collect::<Vec<_>>(),
+                    "split_terminator {:?}", haystack);
+                for n in 0..4 {
+                    assert_eq!(strs(sa.splitn(n, $pat)), haystack.splitn(n, $pat).collect::<Vec<_>>(),
+                        "splitn {} {:?}", n, haystack);
+                }
+                assert_eq!(strs(sa.matches($pat)), haystack.matches($pat).collect::<Vec<_>>());
+                assert_eq!(
+                    sa.match_indices($pat).map(|(idx, m)| (idx, m.as_str())).collect::<Vec<_>>(),
+                    haystack.match_indices($pat).collect::<Vec<_>>());
+                assert_eq!(sa.find($pat), haystack.find($pat));
+                assert_eq!(sa.rfind($pat), haystack.rfind($pat));
+                assert_eq!(sa.contains($pat), haystack.contains($pat));
+                assert_eq!(sa.starts_with($pat), haystack.starts_with($pat));
+                assert_eq!(sa.ends_with($pat), haystack.ends_with($pat));
+                assert_eq!(sa.strip_prefix($pat).map(SoftAsciiStr::as_str), haystack.strip_prefix($pat));
+                assert_eq!(sa.strip_suffix($pat).map(SoftAsciiStr::as_str), haystack.strip_suffix($pat));
+                assert_eq!(sa.split_once($pat).map(|(l, r)| (l.as_str(), r.as_str())),
+                    haystack.split_once($pat));
+                assert_eq!(sa.rsplit_once($pat).map(|(l, r)| (l.as_str(), r.as_str())),
+                    haystack.rsplit_once($pat));
+                assert_eq!(sa.trim_start_matches($pat), haystack.trim_start_matches($pat));
+                assert_eq!(sa.trim_end_matches($pat), haystack.trim_end_matches($pat));
+            }
+        });
+    }
+
+    #[test]
+    fn char_patterns() {
+        assert_same_as_str!(',');
+        assert_same_as_str!('a');
+        assert_same_as_str!(&[',', ' '][..]);
+        assert_same_as_str!([',', 'b']);
+        assert_same_as_str!(&['a', ' ']);
+        for &haystack in HAYSTACKS {
+            assert_eq!(soft(haystack).trim_matches(','), haystack.trim_matches(','));
+        }
+    }
+
+    #[test]
+    fn str_patterns() {
+        assert_same_as_str!(",");
+        assert_same_as_str!("ab");
+        assert_same_as_str!("aa");
+        assert_same_as_str!(", ");
+        assert_same_as_str!("");
+        for &haystack in HAYSTACKS {
+            // `str::trim_matches` does not accept `&str` patterns
+            let expected = haystack.trim_start_matches("a").trim_end_matches("a");
+            assert_eq!(soft(haystack).trim_matches("a"), expected);
+            assert_eq!(soft(haystack).trim_matches(""), haystack);
+        }
+    }
+
+    #[test]
+    fn soft_patterns() {
+        let comma = SoftAsciiChar::from_unchecked(',');
+        let sa = soft("a,b,,c");
+        assert_eq!(strs(sa.split(comma)), vec!["a", "b", "", "c"]);
+        assert_eq!(strs(sa.split(soft(",,"))), vec!["a,b", "c"]);
+        let set = [comma, SoftAsciiChar::from_unchecked('b')];
+        assert_eq!(strs(sa.split(&set[..])), vec!["a", "", "", "", "c"]);
+        assert_eq!(sa.rfind(soft(",")), Some(4));
+        assert_eq!(sa.trim_matches(&set[..]), "a,b,,c");
+    }
+
+    #[test]
+    fn closure_patterns() {
+        let sa = soft("12ab34cd");
+        let is_digit = |ch: SoftAsciiChar| ch.is_digit(10);
+        assert_eq!(strs(sa.split(is_digit)), vec!["", "", "ab", "", "cd"]);
+        assert_eq!(sa.trim_matches(is_digit), "ab34cd");
+        assert_eq!(strs(sa.matches(|ch: SoftAsciiChar| ch.is_alphabetic())),
+            vec!["a", "b", "c", "d"]);
+        let mut calls = 0;
+        assert_eq!(sa.find(|_: SoftAsciiChar| { calls += 1; calls == 3 }), Some(2));
+    }
+
+    #[test]
+    fn results_are_sub_slices() {
+        let sa = soft("a=b");
+        let (key, value) = sa.split_once('=').unwrap();
+        assert_eq!(key.as_ptr(), sa.as_ptr());
+        assert_eq!(value.as_ptr(), sa[2..].as_ptr());
+    }
+}
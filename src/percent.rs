@@ -0,0 +1,315 @@
+//! Percent-encoding ([RFC 3986]) with configurable sets of ascii bytes to encode
+//!
+//! The predefined sets follow the [WHATWG URL Standard], custom sets
+//! can be build from them using `add`/`remove`. All non ascii bytes
+//! and ascii control chars are always encoded, so the output is ascii
+//! by construction.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::percent::{self, EncodeSet};
+//!
+//! let encoded = percent::encode("a b/ä", &EncodeSet::PATH);
+//! assert_eq!(encoded, "a%20b/%C3%A4");
+//!
+//! let encoded = percent::encode("a b/ä", &EncodeSet::COMPONENT);
+//! assert_eq!(encoded, "a%20b%2F%C3%A4");
+//!
+//! let decoded = percent::decode_to_string(&encoded).unwrap();
+//! assert_eq!(decoded, "a b/ä");
+//! ```
+//!
+//! [RFC 3986]: https://tools.ietf.org/html/rfc3986#section-2.1
+//! [WHATWG URL Standard]: https://url.spec.whatwg.org/#percent-encoded-bytes
+use core::fmt::{self, Display};
+use core::str::{self, Utf8Error};
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use hex;
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+/// a set of ascii bytes which have to be percent encoded
+///
+/// Non ascii bytes and ascii control chars are always encoded and can not
+/// be removed from the set.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EncodeSet {
+    mask: u128,
+    space_as_plus: bool
+}
+
+impl EncodeSet {
+
+    /// only the ascii control chars (the C0 control percent-encode set)
+    pub const CONTROLS: EncodeSet = EncodeSet {
+        mask: ((1 << 0x20) - 1) | (1 << 0x7f),
+        space_as_plus: false
+    };
+
+    /// the set for url fragments
+    pub const FRAGMENT: EncodeSet = EncodeSet::CONTROLS
+        .add(b' ').add(b'"').add(b'<').add(b'>').add(b'`');
+
+    /// the set for url queries
+    pub const QUERY: EncodeSet = EncodeSet::CONTROLS
+        .add(b' ').add(b'"').add(b'#').add(b'<').add(b'>');
+
+    /// the set for url paths
+    pub const PATH: EncodeSet = EncodeSet::QUERY
+        .add(b'?').add(b'^').add(b'`').add(b'{').add(b'}');
+
+    /// the set for the userinfo part of urls
+    pub const USERINFO: EncodeSet = EncodeSet::PATH
+        .add(b'/').add(b':').add(b';').add(b'=').add(b'@')
+        .add(b'[').add(b'\\').add(b']').add(b'|');
+
+    /// the set for arbitrary url components (e.g. a single path segment or query value)
+    pub const COMPONENT: EncodeSet = EncodeSet::USERINFO
+        .add(b'$').add(b'%').add(b'&').add(b'+').add(b',');
+
+    /// the set for `application/x-www-form-urlencoded`, encodes spaces as `'+'`
+    pub const FORM_URLENCODED: EncodeSet = EncodeSet {
+        space_as_plus: true,
+        ..EncodeSet::COMPONENT
+            .add(b'!').add(b'\'').add(b'(').add(b')').add(b'~')
+    };
+
+    /// returns a set which additionally encodes `byte`
+    pub const fn add(self, byte: u8) -> EncodeSet {
+        EncodeSet {
+            mask: self.mask | EncodeSet::bit(byte),
+            space_as_plus: self.space_as_plus
+        }
+    }
+
+    /// returns a set which does not encode `byte`
+    ///
+    /// Non ascii bytes and ascii control chars are always encoded, removing
+    /// them has no effect.
+    pub const fn remove(self, byte: u8) -> EncodeSet {
+        EncodeSet {
+            mask: (self.mask & !EncodeSet::bit(byte)) | EncodeSet::CONTROLS.mask,
+            space_as_plus: self.space_as_plus
+        }
+    }
+
+    const fn bit(byte: u8) -> u128 {
+        if byte < 0x80 { 1 << byte } else { 0 }
+    }
+
+    /// returns a set encoding all bytes encoded by either set
+    pub const fn union(self, other: EncodeSet) -> EncodeSet {
+        EncodeSet {
+            mask: self.mask | other.mask,
+            space_as_plus: self.space_as_plus || other.space_as_plus
+        }
+    }
+
+    /// returns true if `byte` has to be encoded
+    pub fn contains(&self, byte: u8) -> bool {
+        !byte.is_ascii() || self.mask & (1 << byte) != 0
+    }
+}
+
+/// percent encodes `input`
+pub fn encode(input: &str, set: &EncodeSet) -> SoftAsciiString {
+    encode_bytes(input.as_bytes(), set)
+}
+
+/// percent encodes the bytes of `input`
+pub fn encode_bytes(input: &[u8], set: &EncodeSet) -> SoftAsciiString {
+    let mut out = SoftAsciiString::with_capacity(input.len());
+    encode_into(input, set, &mut out);
+    out
+}
+
+/// percent encodes `input` appending the result to `out`
+pub fn encode_into(input: &[u8], set: &EncodeSet, out: &mut SoftAsciiString) {
+    for &byte in input {
+        if byte == b' ' && set.space_as_plus {
+            out.push(SoftAsciiChar::rewrap('+'));
+        } else if set.contains(byte) {
+            let escape = [
+                b'%',
+                hex::UPPER_DIGITS[(byte >> 4) as usize],
+                hex::UPPER_DIGITS[(byte & 0xf) as usize]
+            ];
+            let escape = str::from_utf8(&escape).expect("[BUG] escape is ascii");
+            out.push_str(SoftAsciiStr::rewrap(escape));
+        } else {
+            out.push(SoftAsciiChar::rewrap(byte as char));
+        }
+    }
+}
+
+/// Error returned if percent decoding failed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PercentDecodeError {
+    /// the `'%'` at the given byte offset is not followed by two hex digits
+    InvalidEscape(usize),
+    /// the input ends less than two bytes after the `'%'` at the given byte offset
+    TruncatedEscape(usize),
+    /// the decoded bytes are not valid utf-8
+    InvalidUtf8(Utf8Error)
+}
+
+impl Display for PercentDecodeError {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PercentDecodeError::InvalidEscape(idx) =>
+                write!(fter, "invalid percent escape at byte offset {}", idx),
+            PercentDecodeError::TruncatedEscape(idx) =>
+                write!(fter, "truncated percent escape at byte offset {}", idx),
+            PercentDecodeError::InvalidUtf8(ref err) =>
+                write!(fter, "percent decoded data is not utf-8: {}", err)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PercentDecodeError {
+    fn description(&self) -> &str {
+        "percent decoding failed"
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            PercentDecodeError::InvalidUtf8(ref err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+fn decode_impl(input: &SoftAsciiStr, plus_as_space: bool) -> Result<Vec<u8>, PercentDecodeError> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'%' => {
+                if idx + 2 >= bytes.len() {
+                    return Err(PercentDecodeError::TruncatedEscape(idx));
+                }
+                match (hex::digit_value(bytes[idx + 1]), hex::digit_value(bytes[idx + 2])) {
+                    (Some(high), Some(low)) => out.push((high << 4) | low),
+                    _ => return Err(PercentDecodeError::InvalidEscape(idx))
+                }
+                idx += 3;
+            },
+            b'+' if plus_as_space => {
+                out.push(b' ');
+                idx += 1;
+            },
+            byte => {
+                out.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// percent decodes `input`
+pub fn decode(input: &SoftAsciiStr) -> Result<Vec<u8>, PercentDecodeError> {
+    decode_impl(input, false)
+}
+
+/// percent decodes `input` and validates that the result is utf-8
+pub fn decode_to_string(input: &SoftAsciiStr) -> Result<String, PercentDecodeError> {
+    let bytes = decode_impl(input, false)?;
+    String::from_utf8(bytes)
+        .map_err(|err| PercentDecodeError::InvalidUtf8(err.utf8_error()))
+}
+
+/// decodes `application/x-www-form-urlencoded` data, i.e. `'+'` is decoded as space
+pub fn decode_form(input: &SoftAsciiStr) -> Result<String, PercentDecodeError> {
+    let bytes = decode_impl(input, true)?;
+    String::from_utf8(bytes)
+        .map_err(|err| PercentDecodeError::InvalidUtf8(err.utf8_error()))
+}
+
+#[cfg(test)]
+mod test {
+    use macros::soft;
+    use super::{
+        encode, encode_bytes, decode, decode_to_string, decode_form,
+        EncodeSet, PercentDecodeError
+    };
+
+    #[test]
+    fn sets() {
+        let input = "a b\"#<>?^`{}/:;=@[\\]|$%&+,!'()~*";
+        assert_eq!(
+            encode(input, &EncodeSet::QUERY),
+            "a%20b%22%23%3C%3E?^`{}/:;=@[\\]|$%&+,!'()~*"
+        );
+        assert_eq!(
+            encode(input, &EncodeSet::PATH),
+            "a%20b%22%23%3C%3E%3F%5E%60%7B%7D/:;=@[\\]|$%&+,!'()~*"
+        );
+        assert_eq!(
+            encode(input, &EncodeSet::USERINFO),
+            "a%20b%22%23%3C%3E%3F%5E%60%7B%7D%2F%3A%3B%3D%40%5B%5C%5D%7C$%&+,!'()~*"
+        );
+        assert_eq!(
+            encode(input, &EncodeSet::FORM_URLENCODED),
+            concat!(
+                "a+b%22%23%3C%3E%3F%5E%60%7B%7D%2F%3A%3B%3D%40%5B%5C%5D%7C",
+                "%24%25%26%2B%2C%21%27%28%29%7E*"
+            )
+        );
+    }
+
+    #[test]
+    fn controls_and_non_ascii_are_always_encoded() {
+        assert_eq!(encode_bytes(b"\x00\x1f\x7f\x80\xff", &EncodeSet::CONTROLS), "%00%1F%7F%80%FF");
+        assert_eq!(encode("ä", &EncodeSet::CONTROLS.remove(0xc3)), "%C3%A4");
+        let set = EncodeSet::FRAGMENT.remove(0x00).remove(0x1f).remove(0x7f);
+        assert_eq!(set, EncodeSet::FRAGMENT);
+        assert_eq!(encode_bytes(b"\x00\x1f\x7f", &set), "%00%1F%7F");
+    }
+
+    #[test]
+    fn custom_set() {
+        let set = EncodeSet::CONTROLS.add(b'x').union(EncodeSet::CONTROLS.add(b'y'));
+        assert_eq!(encode("axyz", &set), "a%78%79z");
+        assert_eq!(encode("a b", &EncodeSet::FRAGMENT.remove(b' ')), "a b");
+    }
+
+    #[test]
+    fn decoding() {
+        assert_eq!(assert_ok!(decode(soft("a%20b%c3%A4+"))), "a bä+".as_bytes());
+        assert_eq!(assert_ok!(decode_to_string(soft("a%20b%C3%A4"))), "a bä");
+        assert_eq!(assert_ok!(decode_form(soft("a+b%2B"))), "a b+");
+    }
+
+    #[test]
+    fn malformed_escapes() {
+        assert_eq!(assert_err!(decode(soft("ab%4"))), PercentDecodeError::TruncatedEscape(2));
+        assert_eq!(assert_err!(decode(soft("ab%"))), PercentDecodeError::TruncatedEscape(2));
+        assert_eq!(assert_err!(decode(soft("a%4g%20"))), PercentDecodeError::InvalidEscape(1));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let err = assert_err!(decode_to_string(soft("ab%C3")));
+        match err {
+            PercentDecodeError::InvalidUtf8(err) => assert_eq!(err.valid_up_to(), 2),
+            other => panic!("unexpected error: {:?}", other)
+        }
+    }
+
+    #[test]
+    fn roundtrip_bytes() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let encoded = encode_bytes(&bytes, &EncodeSet::FORM_URLENCODED);
+        assert!(encoded.is_ascii());
+        assert_eq!(assert_ok!(super::decode_impl(&encoded, true)), bytes);
+    }
+}
@@ -0,0 +1,374 @@
+//! Quoted-printable content transfer encoding ([RFC 2045])
+//!
+//! The [`Encoder`](struct.Encoder.html) works streaming, i.e. the data
+//! can be passed to it in arbitrary chunks, and writes the encoded data
+//! to a `SoftAsciiString` or (through `FmtWrite`) any `fmt::Write`
+//! implementation. Lines are broken with soft line breaks so that no
+//! line exceeds 76 chars.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::quoted_printable::{self, Encoder, Mode};
+//! use soft_ascii_string::SoftAsciiString;
+//!
+//! let encoded = quoted_printable::encode(b"Gr\xc3\xbc\xc3\x9fe \r\n", Mode::Text);
+//! assert_eq!(encoded, "Gr=C3=BC=C3=9Fe=20\r\n");
+//!
+//! let mut out = SoftAsciiString::new();
+//! {
+//!     let mut encoder = Encoder::new(&mut out, Mode::Binary);
+//!     encoder.write(b"a=b").unwrap();
+//!     encoder.write(b"\r\n").unwrap();
+//!     encoder.finish().unwrap();
+//! }
+//! assert_eq!(out, "a=3Db=0D=0A");
+//!
+//! assert_eq!(quoted_printable::decode(&out).unwrap(), b"a=b\r\n");
+//! ```
+//!
+//! [RFC 2045]: https://tools.ietf.org/html/rfc2045#section-6.7
+use core::fmt::{self, Display};
+use core::str;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use hex;
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+/// the maximal length of an encoded line, excluding the line break
+pub const MAX_LINE_LEN: usize = 76;
+
+/// how line breaks in the input are handled
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// `"\r\n"` and `"\n"` in the input are written as (CRLF) line breaks
+    Text,
+    /// all bytes including CR and LF are encoded
+    Binary
+}
+
+/// a destination for the output of the `Encoder`
+pub trait Output {
+    /// appends the given ascii text
+    fn write_ascii(&mut self, ascii: &SoftAsciiStr) -> fmt::Result;
+}
+
+impl Output for SoftAsciiString {
+    fn write_ascii(&mut self, ascii: &SoftAsciiStr) -> fmt::Result {
+        self.push_str(ascii);
+        Ok(())
+    }
+}
+
+/// adapter to use any `fmt::Write` implementation as `Output`
+///
+/// ```
+/// use soft_ascii_string::quoted_printable::{Encoder, FmtWrite, Mode};
+///
+/// let mut out = FmtWrite(String::new());
+/// {
+///     let mut encoder = Encoder::new(&mut out, Mode::Text);
+///     encoder.write(b"a=b").unwrap();
+///     encoder.finish().unwrap();
+/// }
+/// assert_eq!(out.0, "a=3Db");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FmtWrite<W>(pub W);
+
+impl<W> Output for FmtWrite<W>
+    where W: fmt::Write
+{
+    fn write_ascii(&mut self, ascii: &SoftAsciiStr) -> fmt::Result {
+        self.0.write_str(ascii.as_str())
+    }
+}
+
+/// streaming quoted-printable encoder
+///
+/// As whitespace at the end of a line has to be encoded the last
+/// whitespace byte (and in text mode a trailing `'\r'`) is only written
+/// once the next chunk or `finish` is called, so `finish` has to be
+/// called once all data was written.
+pub struct Encoder<'a, O: Output + ?Sized + 'a> {
+    out: &'a mut O,
+    mode: Mode,
+    line_len: usize,
+    pending_whitespace: Option<u8>,
+    pending_cr: bool
+}
+
+impl<'a, O> Encoder<'a, O>
+    where O: Output + ?Sized
+{
+
+    /// creates a new encoder writing to `out`
+    pub fn new(out: &'a mut O, mode: Mode) -> Self {
+        Encoder {
+            out, mode,
+            line_len: 0,
+            pending_whitespace: None,
+            pending_cr: false
+        }
+    }
+
+    /// encodes `data`
+    pub fn write(&mut self, data: &[u8]) -> fmt::Result {
+        for &byte in data {
+            if self.mode == Mode::Text {
+                if self.pending_cr {
+                    self.pending_cr = false;
+                    if byte == b'\n' {
+                        self.hard_line_break()?;
+                        continue;
+                    }
+                    self.flush_whitespace(false)?;
+                    self.write_byte(b'\r')?;
+                }
+                match byte {
+                    b'\r' => {
+                        self.pending_cr = true;
+                        continue;
+                    },
+                    b'\n' => {
+                        self.hard_line_break()?;
+                        continue;
+                    },
+                    _ => {}
+                }
+            }
+            self.flush_whitespace(false)?;
+            if byte == b' ' || byte == b'\t' {
+                self.pending_whitespace = Some(byte);
+            } else {
+                self.write_byte(byte)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// writes all pending data
+    ///
+    /// The output does not end with a line break except if the input did.
+    pub fn finish(mut self) -> fmt::Result {
+        if self.pending_cr {
+            self.pending_cr = false;
+            self.flush_whitespace(false)?;
+            self.write_byte(b'\r')?;
+        }
+        self.flush_whitespace(true)
+    }
+
+    fn hard_line_break(&mut self) -> fmt::Result {
+        self.flush_whitespace(true)?;
+        self.line_len = 0;
+        self.out.write_ascii(SoftAsciiStr::rewrap("\r\n"))
+    }
+
+    fn flush_whitespace(&mut self, at_line_end: bool) -> fmt::Result {
+        match self.pending_whitespace.take() {
+            Some(byte) if at_line_end => self.write_encoded(byte),
+            Some(byte) => self.write_token(&[byte]),
+            None => Ok(())
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) -> fmt::Result {
+        if (b'!'..=b'~').contains(&byte) && byte != b'=' {
+            self.write_token(&[byte])
+        } else {
+            self.write_encoded(byte)
+        }
+    }
+
+    fn write_encoded(&mut self, byte: u8) -> fmt::Result {
+        self.write_token(&[
+            b'=',
+            hex::UPPER_DIGITS[(byte >> 4) as usize],
+            hex::UPPER_DIGITS[(byte & 0xf) as usize]
+        ])
+    }
+
+    fn write_token(&mut self, token: &[u8]) -> fmt::Result {
+        // keep space for the `'='` of a soft line break
+        if self.line_len + token.len() > MAX_LINE_LEN - 1 {
+            self.out.write_ascii(SoftAsciiStr::rewrap("=\r\n"))?;
+            self.line_len = 0;
+        }
+        self.line_len += token.len();
+        let token = str::from_utf8(token).expect("[BUG] tokens are ascii");
+        self.out.write_ascii(SoftAsciiStr::rewrap(token))
+    }
+}
+
+/// encodes `input` as quoted-printable
+pub fn encode(input: &[u8], mode: Mode) -> SoftAsciiString {
+    let mut out = SoftAsciiString::with_capacity(input.len() + input.len() / 4);
+    {
+        let mut encoder = Encoder::new(&mut out, mode);
+        encoder.write(input).and_then(|()| encoder.finish())
+            .expect("[BUG] writing to a SoftAsciiString can not fail");
+    }
+    out
+}
+
+/// Error returned if quoted-printable decoding failed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum QuotedPrintableError {
+    /// the `'='` at the given byte offset is neither followed by two
+    /// hex digits nor part of a soft line break
+    InvalidEscape(usize)
+}
+
+impl Display for QuotedPrintableError {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            QuotedPrintableError::InvalidEscape(idx) =>
+                write!(fter, "invalid quoted-printable escape at byte offset {}", idx)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for QuotedPrintableError {
+    fn description(&self) -> &str {
+        "quoted-printable decoding failed"
+    }
+}
+
+/// decodes the quoted-printable `input`
+///
+/// Trailing whitespace on a line (which might have been added during
+/// transport) is removed, hard line breaks are kept as they are.
+/// Lowercase hex digits are accepted.
+pub fn decode(input: &SoftAsciiStr) -> Result<Vec<u8>, QuotedPrintableError> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut line_start = 0;
+    while line_start < bytes.len() {
+        let (line_end, next_line_start) = match bytes[line_start..].iter().position(|&b| b == b'\n') {
+            Some(pos) => (line_start + pos, line_start + pos + 1),
+            None => (bytes.len(), bytes.len())
+        };
+        let mut content_end = line_end;
+        if content_end > line_start && bytes[content_end - 1] == b'\r' && line_end < bytes.len() {
+            content_end -= 1;
+        }
+        let line_break = &bytes[content_end..next_line_start];
+        while content_end > line_start && (bytes[content_end - 1] == b' ' || bytes[content_end - 1] == b'\t') {
+            content_end -= 1;
+        }
+        let soft_break = content_end > line_start && bytes[content_end - 1] == b'=';
+        if soft_break {
+            content_end -= 1;
+        }
+
+        let mut idx = line_start;
+        while idx < content_end {
+            if bytes[idx] == b'=' {
+                let escape = bytes.get(idx + 1..idx + 3).filter(|_| idx + 3 <= content_end);
+                match escape.map(|hex| (hex::digit_value(hex[0]), hex::digit_value(hex[1]))) {
+                    Some((Some(high), Some(low))) => out.push((high << 4) | low),
+                    _ => return Err(QuotedPrintableError::InvalidEscape(idx))
+                }
+                idx += 3;
+            } else {
+                out.push(bytes[idx]);
+                idx += 1;
+            }
+        }
+        if !soft_break {
+            out.extend_from_slice(line_break);
+        }
+        line_start = next_line_start;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use macros::soft;
+    use soft_string::SoftAsciiString;
+    use super::{encode, decode, Encoder, FmtWrite, Mode, QuotedPrintableError, MAX_LINE_LEN};
+
+    #[test]
+    fn encode_special_chars() {
+        assert_eq!(encode(b"a=b\x00\x7f\xff~!", Mode::Text), "a=3Db=00=7F=FF~!");
+    }
+
+    #[test]
+    fn encode_trailing_whitespace() {
+        assert_eq!(encode(b"a b \r\nc\t\nd ", Mode::Text), "a b=20\r\nc=09\r\nd=20");
+        assert_eq!(encode(b"a  \rb", Mode::Text), "a  =0Db");
+    }
+
+    #[test]
+    fn encode_binary_mode() {
+        assert_eq!(encode(b"a \r\nb\n", Mode::Binary), "a =0D=0Ab=0A");
+    }
+
+    #[test]
+    fn encode_is_streaming() {
+        let input = b"line one \r\nline two\t \r\n\xc3\xa4=\r";
+        let expected = encode(input, Mode::Text);
+        for split in 0..input.len() {
+            let mut out = SoftAsciiString::new();
+            {
+                let mut encoder = Encoder::new(&mut out, Mode::Text);
+                assert_ok!(encoder.write(&input[..split]));
+                assert_ok!(encoder.write(&input[split..]));
+                assert_ok!(encoder.finish());
+            }
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn encode_into_fmt_write() {
+        let mut string = String::new();
+        {
+            let mut out = FmtWrite(&mut string);
+            let mut encoder = Encoder::new(&mut out, Mode::Text);
+            assert_ok!(encoder.write(b"\xc3\xa4 "));
+            assert_ok!(encoder.finish());
+        }
+        assert_eq!(string, "=C3=A4=20");
+    }
+
+    #[test]
+    fn soft_line_breaks() {
+        let input: Vec<u8> = b"0123456789".iter().cycle().take(200).cloned()
+            .chain(b"\xff".iter().cycle().take(60).cloned())
+            .collect();
+        let encoded = encode(&input, Mode::Binary);
+        let lines: Vec<_> = encoded.as_str().split("\r\n").collect();
+        assert!(lines.len() > 3);
+        for line in &lines[..lines.len() - 1] {
+            assert!(line.len() <= MAX_LINE_LEN, "{:?}", line);
+            assert!(line.ends_with('='));
+        }
+        assert_eq!(assert_ok!(decode(&encoded)), input);
+    }
+
+    #[test]
+    fn decode_line_breaks_and_whitespace() {
+        assert_eq!(assert_ok!(decode(soft("a=20b  \r\nc=\r\nd= \ne=\n"))), b"a b\r\ncde");
+        assert_eq!(assert_ok!(decode(soft("=c3=a4"))), b"\xc3\xa4");
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(assert_err!(decode(soft("ab=4"))), QuotedPrintableError::InvalidEscape(2));
+        assert_eq!(assert_err!(decode(soft("a\r\nb=XY"))), QuotedPrintableError::InvalidEscape(4));
+        assert_eq!(assert_err!(decode(soft("a=4\r\n"))), QuotedPrintableError::InvalidEscape(1));
+    }
+
+    #[test]
+    fn roundtrip_text() {
+        let input = "Grüße \t\r\nmit = und \r\n\r\nEnde\r\n".as_bytes();
+        assert_eq!(assert_ok!(decode(&encode(input, Mode::Text))), input);
+    }
+}
@@ -0,0 +1,260 @@
+//! serde support for `SoftAsciiChar`, `SoftAsciiStr` and `SoftAsciiString`
+//!
+//! This module is only available if the `serde` feature is enabled.
+//!
+//! The default `Deserialize` implementations validate the input, i.e.
+//! they behave like `SoftAsciiString::from_string`/`SoftAsciiStr::from_str`
+//! and fail if the input contains non us-ascii chars. If you do not want
+//! to pay for the check (e.g. because the data was written by this program)
+//! you can use the [`unchecked`](unchecked/index.html) module with
+//! `#[serde(with = "soft_ascii_string::serde_support::unchecked")]`, which
+//! behaves like `from_unchecked`.
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer};
+
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+#[cfg(feature = "alloc")]
+use soft_string::SoftAsciiString;
+
+impl Serialize for SoftAsciiChar {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_char((*self).into())
+    }
+}
+
+impl Serialize for SoftAsciiStr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for SoftAsciiString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for SoftAsciiString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let string = String::deserialize(deserializer)?;
+        SoftAsciiString::from_string(string)
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Box<SoftAsciiStr> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        SoftAsciiString::deserialize(deserializer)
+            .map(SoftAsciiString::into_boxed_soft_ascii_str)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a SoftAsciiStr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let string = <&'a str>::deserialize(deserializer)?;
+        SoftAsciiStr::from_str(string)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// (de-)serialization without validating the soft constraint
+///
+/// To be used with `#[serde(with = "soft_ascii_string::serde_support::unchecked")]`
+/// (or `deserialize_with`) on fields of type `SoftAsciiString`,
+/// `Box<SoftAsciiStr>` or `&'de SoftAsciiStr`.
+///
+/// Serialization is the same as the default serialization, deserialization
+/// behaves like `from_unchecked`, i.e. it won't fail for non us-ascii input.
+pub mod unchecked {
+    use serde::ser::{Serialize, Serializer};
+    use serde::de::Deserializer;
+
+    pub use self::hidden::UncheckedDeserialize;
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: ?Sized + Serialize, S: Serializer
+    {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where T: UncheckedDeserialize<'de>, D: Deserializer<'de>
+    {
+        T::deserialize_unchecked(deserializer)
+    }
+
+    mod hidden {
+        #[cfg(feature = "alloc")]
+        use alloc::boxed::Box;
+        #[cfg(feature = "alloc")]
+        use alloc::string::String;
+        use serde::de::{Deserialize, Deserializer};
+
+        use soft_str::SoftAsciiStr;
+        #[cfg(feature = "alloc")]
+        use soft_string::SoftAsciiString;
+
+        mod sealed {
+            /// not nameable outside of this crate, so `UncheckedDeserialize` can't be implemented there
+            pub trait Sealed {}
+        }
+
+        /// Types which can be deserialized without validating the soft constraint.
+        ///
+        /// This trait is sealed, i.e. it can not be implemented outside of
+        /// this crate, it's only used as bound for `unchecked::deserialize`.
+        ///
+        /// ```compile_fail,E0277
+        /// extern crate serde;
+        /// extern crate soft_ascii_string;
+        /// use serde::de::Deserializer;
+        /// use soft_ascii_string::serde_support::unchecked::UncheckedDeserialize;
+        ///
+        /// struct Custom;
+        ///
+        /// impl<'de> UncheckedDeserialize<'de> for Custom {
+        ///     fn deserialize_unchecked<D>(_: D) -> Result<Self, D::Error>
+        ///         where D: Deserializer<'de>
+        ///     {
+        ///         Ok(Custom)
+        ///     }
+        /// }
+        /// # fn main() {}
+        /// ```
+        pub trait UncheckedDeserialize<'de>: sealed::Sealed + Sized {
+            #[doc(hidden)]
+            fn deserialize_unchecked<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>;
+        }
+
+        #[cfg(feature = "alloc")]
+        impl sealed::Sealed for SoftAsciiString {}
+        #[cfg(feature = "alloc")]
+        impl sealed::Sealed for Box<SoftAsciiStr> {}
+        impl sealed::Sealed for &SoftAsciiStr {}
+
+        #[cfg(feature = "alloc")]
+        impl<'de> UncheckedDeserialize<'de> for SoftAsciiString {
+            fn deserialize_unchecked<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>
+            {
+                String::deserialize(deserializer)
+                    .map(SoftAsciiString::from_unchecked)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de> UncheckedDeserialize<'de> for Box<SoftAsciiStr> {
+            fn deserialize_unchecked<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>
+            {
+                Box::<str>::deserialize(deserializer)
+                    .map(SoftAsciiStr::from_boxed_str)
+            }
+        }
+
+        impl<'de: 'a, 'a> UncheckedDeserialize<'de> for &'a SoftAsciiStr {
+            fn deserialize_unchecked<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>
+            {
+                <&'a str>::deserialize(deserializer)
+                    .map(SoftAsciiStr::from_unchecked)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use serde_test::{
+        Token,
+        assert_tokens, assert_ser_tokens,
+        assert_de_tokens, assert_de_tokens_error
+    };
+
+    use soft_char::SoftAsciiChar;
+    use soft_str::SoftAsciiStr;
+    use soft_string::SoftAsciiString;
+
+    #[test]
+    fn soft_ascii_char() {
+        let ch = SoftAsciiChar::from_unchecked('a');
+        assert_ser_tokens(&ch, &[Token::Char('a')]);
+    }
+
+    #[test]
+    fn soft_ascii_string() {
+        let string = SoftAsciiString::from_unchecked("hy there");
+        assert_tokens(&string, &[Token::Str("hy there")]);
+        assert_de_tokens(&string, &[Token::String("hy there")]);
+    }
+
+    #[test]
+    fn soft_ascii_string_rejects_non_ascii() {
+        assert_de_tokens_error::<SoftAsciiString>(
+            &[Token::Str("a↓")],
+            "source contains non us-ascii char '↓' at byte offset 1:\n    a↓\n     ^"
+        );
+    }
+
+    #[test]
+    fn boxed_soft_ascii_str() {
+        let boxed = SoftAsciiString::from_unchecked("hy").into_boxed_soft_ascii_str();
+        assert_tokens(&boxed, &[Token::Str("hy")]);
+        assert_de_tokens_error::<Box<SoftAsciiStr>>(
+            &[Token::Str("↓")],
+            "source contains non us-ascii char '↓' at byte offset 0:\n    ↓\n    ^"
+        );
+    }
+
+    #[test]
+    fn borrowed_soft_ascii_str() {
+        let borrowed = SoftAsciiStr::from_unchecked("hy");
+        assert_tokens(&borrowed, &[Token::BorrowedStr("hy")]);
+        assert_de_tokens_error::<&SoftAsciiStr>(
+            &[Token::BorrowedStr("↓")],
+            "source contains non us-ascii char '↓' at byte offset 0:\n    ↓\n    ^"
+        );
+    }
+
+    #[cfg(not(feature = "check-unchecked"))]
+    #[test]
+    fn unchecked_does_not_validate() {
+        use serde::de::{Deserialize, Deserializer};
+        use super::unchecked;
+
+        #[derive(Debug, PartialEq)]
+        struct Unchecked(SoftAsciiString);
+
+        impl<'de> Deserialize<'de> for Unchecked {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>
+            {
+                unchecked::deserialize(deserializer).map(Unchecked)
+            }
+        }
+
+        let value = Unchecked(SoftAsciiString::from_unchecked("a↓"));
+        assert_de_tokens(&value, &[Token::Str("a↓")]);
+    }
+}
@@ -0,0 +1,250 @@
+use core::ops::{Deref, Range, RangeBounds, Bound};
+use alloc::borrow::ToOwned;
+use alloc::sync::Arc;
+
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+/// a reference counted, sliceable `SoftAsciiStr`
+///
+/// It's an `Arc<SoftAsciiStr>` plus a range into it, so `clone` and
+/// `slice` are O(1) and don't copy the underlying value, which makes it
+/// suited for splitting a large input (e.g. a header block) into many
+/// owned parts.
+///
+/// ```
+/// use soft_ascii_string::{SoftAsciiShared, SoftAsciiString};
+///
+/// let block = SoftAsciiShared::from(SoftAsciiString::from_unchecked("Subject: hy\r\n"));
+/// let (name, value) = block.trim_end().split_once(": ").unwrap();
+/// let name = block.slice_ref(name);
+/// let value = block.slice_ref(value);
+/// assert_eq!(name, "Subject");
+/// assert_eq!(value, "hy");
+/// drop(block);
+/// // both still point into the original buffer
+/// assert_eq!(value.source().as_str(), "Subject: hy\r\n");
+/// ```
+#[derive(Clone)]
+pub struct SoftAsciiShared {
+    source: Arc<SoftAsciiStr>,
+    // always on char boundaries of `source`
+    start: usize,
+    end: usize
+}
+
+impl SoftAsciiShared {
+
+    /// creates a `SoftAsciiShared` covering all of `source`
+    #[inline]
+    pub fn new(source: Arc<SoftAsciiStr>) -> Self {
+        let end = source.len();
+        SoftAsciiShared { source, start: 0, end }
+    }
+
+    /// returns a `SoftAsciiShared` for the given part of `self`, without copying
+    ///
+    /// The range is relative to `self` (not to the source).
+    ///
+    /// # Panics
+    ///
+    /// like indexing a `str`, if the range is out of bounds or not on a char boundary
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> SoftAsciiShared {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1)
+                .expect("attempted to index str from after maximum usize"),
+            Bound::Unbounded => 0
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1)
+                .expect("attempted to index str up to maximum usize"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len()
+        };
+        // panics like str indexing if the range is invalid
+        let _ = &self.as_soft_ascii_str()[start..end];
+        SoftAsciiShared {
+            source: self.source.clone(),
+            start: self.start + start,
+            end: self.start + end
+        }
+    }
+
+    /// returns a `SoftAsciiShared` for `subset`, which has to be a part of `self`
+    ///
+    /// This is meant to be used with the result of borrowing methods like
+    /// `split`, `trim` or `find` called on `self`.
+    ///
+    /// # Panics
+    ///
+    /// if `subset` does not point into `self`
+    pub fn slice_ref(&self, subset: &SoftAsciiStr) -> SoftAsciiShared {
+        let own_start = self.as_ptr() as usize;
+        let sub_start = subset.as_ptr() as usize;
+        assert!(
+            sub_start >= own_start && sub_start + subset.len() <= own_start + self.len(),
+            "subset is not a part of this SoftAsciiShared"
+        );
+        let start = sub_start - own_start;
+        self.slice(start..start + subset.len())
+    }
+
+    /// returns the value this is a part of
+    #[inline]
+    pub fn source(&self) -> &Arc<SoftAsciiStr> {
+        &self.source
+    }
+
+    /// returns the range in the source this is covering
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    #[inline]
+    pub fn as_soft_ascii_str(&self) -> &SoftAsciiStr {
+        SoftAsciiStr::rewrap(&self.source.as_str()[self.start..self.end])
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.as_soft_ascii_str().as_str()
+    }
+}
+
+impl Deref for SoftAsciiShared {
+    type Target = SoftAsciiStr;
+
+    #[inline]
+    fn deref(&self) -> &SoftAsciiStr {
+        self.as_soft_ascii_str()
+    }
+}
+
+impl_soft_ascii_str_traits!([] SoftAsciiShared);
+
+impl From<Arc<SoftAsciiStr>> for SoftAsciiShared {
+    #[inline]
+    fn from(source: Arc<SoftAsciiStr>) -> Self {
+        SoftAsciiShared::new(source)
+    }
+}
+
+impl From<SoftAsciiString> for SoftAsciiShared {
+    #[inline]
+    fn from(string: SoftAsciiString) -> Self {
+        SoftAsciiShared::new(Arc::from(string))
+    }
+}
+
+impl<'a> From<&'a SoftAsciiStr> for SoftAsciiShared {
+    #[inline]
+    fn from(s: &'a SoftAsciiStr) -> Self {
+        SoftAsciiShared::new(Arc::from(s))
+    }
+}
+
+impl From<SoftAsciiShared> for SoftAsciiString {
+    #[inline]
+    fn from(shared: SoftAsciiShared) -> Self {
+        shared.as_soft_ascii_str().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::vec::Vec;
+    use ::{SoftAsciiStr, SoftAsciiString};
+    use super::SoftAsciiShared;
+
+    #[test]
+    fn slice_and_clone_share_the_source() {
+        let shared = SoftAsciiShared::from(SoftAsciiString::from_unchecked("To: a@b.c\r\n"));
+        let value = shared.slice(4..9);
+        assert_eq!(value, "a@b.c");
+        assert_eq!(value.range(), 4..9);
+        let domain = value.slice(2..);
+        assert_eq!(domain, "b.c");
+        assert_eq!(domain.range(), 6..9);
+        assert_eq!(value.slice(..=0), "a");
+        let clone = domain.clone();
+        assert!(Arc::ptr_eq(clone.source(), shared.source()));
+        assert_eq!(Arc::strong_count(shared.source()), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_out_of_bounds() {
+        let shared = SoftAsciiShared::from(SoftAsciiStr::from_unchecked("abc"));
+        let _ = shared.slice(1..2).slice(0..2);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempted to index str up to maximum usize")]
+    fn slice_inclusive_up_to_usize_max() {
+        let shared = SoftAsciiShared::from(SoftAsciiStr::from_unchecked("abc"));
+        let _ = shared.slice(..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempted to index str from after maximum usize")]
+    fn slice_exclusive_from_usize_max() {
+        use std::ops::Bound;
+        let shared = SoftAsciiShared::from(SoftAsciiStr::from_unchecked("abc"));
+        let _ = shared.slice((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    #[should_panic(expected = "char boundary")]
+    fn slice_not_on_char_boundary() {
+        let shared = SoftAsciiShared::from(SoftAsciiStr::rewrap("a↓"));
+        let _ = shared.slice(2..);
+    }
+
+    #[test]
+    fn slice_ref_of_split_parts() {
+        let block = SoftAsciiShared::from(SoftAsciiStr::from_unchecked("a: 1\r\nb: 2\r\n"));
+        let lines: Vec<SoftAsciiShared> = block.lines()
+            .map(|line| block.slice_ref(line))
+            .collect();
+        assert_eq!(lines, vec!["a: 1", "b: 2"]);
+        assert_eq!(lines[1].range(), 6..10);
+        let value = lines[1].slice_ref(lines[1].split_once(": ").unwrap().1);
+        assert_eq!(value, "2");
+        assert_eq!(value.range(), 9..10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_ref_of_other_value() {
+        let block = SoftAsciiShared::from(SoftAsciiStr::from_unchecked("abc"));
+        let _ = block.slice_ref(SoftAsciiStr::from_unchecked("abc"));
+    }
+
+    #[test]
+    fn eq_hash_like_soft_ascii_str() {
+        let shared = SoftAsciiShared::from(SoftAsciiStr::from_unchecked("xabcx")).slice(1..4);
+        let mut set = HashSet::new();
+        set.insert(shared.clone());
+        assert!(set.contains(SoftAsciiStr::from_unchecked("abc")));
+        assert_eq!(shared, SoftAsciiShared::from(SoftAsciiStr::from_unchecked("abc")));
+        assert_eq!(format!("{} {:?}", shared, shared), "abc \"abc\"");
+        assert_eq!(SoftAsciiString::from(shared), "abc");
+    }
+
+    #[test]
+    fn arc_and_rc_conversions() {
+        let arc: Arc<SoftAsciiStr> = SoftAsciiString::from_unchecked("abc").into();
+        assert_eq!(&*arc, "abc");
+        let arc: Arc<SoftAsciiStr> = SoftAsciiStr::from_unchecked("abc").into();
+        assert_eq!(SoftAsciiShared::from(arc), "abc");
+        let rc: Rc<SoftAsciiStr> = SoftAsciiString::from_unchecked("abc").into();
+        assert_eq!(&*rc, "abc");
+        let rc: Rc<SoftAsciiStr> = SoftAsciiStr::from_unchecked("abc").into();
+        assert_eq!(&*rc, "abc");
+    }
+}
@@ -0,0 +1,415 @@
+use core::iter::{FromIterator, Extend};
+use core::ops::{Deref, DerefMut};
+use core::str;
+use alloc::borrow::ToOwned;
+
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+const INLINE_CAPACITY: usize = 23;
+
+/// a `SoftAsciiString` storing short values inline
+///
+/// Values of up to `INLINE_CAPACITY` (23) bytes are stored inline, longer
+/// values are transparently moved into a (heap allocated) `SoftAsciiString`.
+/// Once moved to the heap it stays there (even if it's shortened), just
+/// like `SoftAsciiString` does not shrink automatically.
+///
+/// ```
+/// use soft_ascii_string::{SoftAsciiSmallString, SoftAsciiStr};
+///
+/// let mut mime = SoftAsciiSmallString::from(SoftAsciiStr::from_str_const("text"));
+/// mime.push_str(SoftAsciiStr::from_str_const("/plain"));
+/// assert!(mime.is_inline());
+/// assert_eq!(mime, "text/plain");
+/// ```
+#[derive(Clone)]
+pub struct SoftAsciiSmallString {
+    repr: Repr
+}
+
+#[derive(Clone)]
+enum Repr {
+    // `buf[..len]` is always valid utf-8 as only whole chars/strs are appended
+    Inline { len: u8, buf: [u8; INLINE_CAPACITY] },
+    Heap(SoftAsciiString)
+}
+
+impl SoftAsciiSmallString {
+
+    /// number of bytes which can be stored without allocating
+    pub const INLINE_CAPACITY: usize = INLINE_CAPACITY;
+
+    /// creates an empty (inline) `SoftAsciiSmallString`
+    #[inline]
+    pub const fn new() -> Self {
+        SoftAsciiSmallString { repr: Repr::Inline { len: 0, buf: [0; INLINE_CAPACITY] } }
+    }
+
+    /// creates an empty `SoftAsciiSmallString`, which is on the heap if `capacity` isn't inline
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= INLINE_CAPACITY {
+            SoftAsciiSmallString::new()
+        } else {
+            SoftAsciiSmallString { repr: Repr::Heap(SoftAsciiString::with_capacity(capacity)) }
+        }
+    }
+
+    #[inline(always)]
+    #[cfg_attr(any(feature = "check-unchecked", feature = "track-unchecked", debug_assertions), track_caller)]
+    pub fn from_unchecked(s: &str) -> Self {
+        SoftAsciiSmallString::from(SoftAsciiStr::from_unchecked(s))
+    }
+
+    /// returns true if the value is stored inline
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        match self.repr {
+            Repr::Inline { .. } => true,
+            Repr::Heap(_) => false
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        match self.repr {
+            Repr::Inline { .. } => INLINE_CAPACITY,
+            Repr::Heap(ref string) => string.capacity()
+        }
+    }
+
+    /// makes sure at least `additional` more bytes can be appended without reallocating
+    ///
+    /// # Panics
+    ///
+    /// if the new capacity overflows `usize`
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len().checked_add(additional).expect("capacity overflow");
+        match self.repr {
+            Repr::Inline { .. } => {
+                if needed > INLINE_CAPACITY {
+                    self.spill(needed)
+                }
+            },
+            Repr::Heap(ref mut string) => string.reserve(additional)
+        }
+    }
+
+    /// moves the value to the heap
+    fn spill(&mut self, capacity: usize) {
+        let mut string = SoftAsciiString::with_capacity(capacity.max(2 * INLINE_CAPACITY));
+        string.push_str(self.as_soft_ascii_str());
+        self.repr = Repr::Heap(string);
+    }
+
+    pub fn push(&mut self, ch: SoftAsciiChar) {
+        match self.repr {
+            Repr::Inline { ref mut len, ref mut buf }
+                if (*len as usize) < INLINE_CAPACITY && ch.is_ascii() =>
+            {
+                buf[*len as usize] = char::from(ch) as u8;
+                *len += 1;
+                return;
+            },
+            Repr::Heap(ref mut string) => return string.push(ch),
+            _ => {}
+        }
+        let mut buf = [0u8; 4];
+        let encoded = char::from(ch).encode_utf8(&mut buf);
+        self.push_str(SoftAsciiStr::rewrap(encoded));
+    }
+
+    pub fn push_str(&mut self, s: &SoftAsciiStr) {
+        self.reserve(s.len());
+        match self.repr {
+            Repr::Inline { ref mut len, ref mut buf } => {
+                let start = *len as usize;
+                buf[start..start + s.len()].copy_from_slice(s.as_bytes());
+                *len += s.len() as u8;
+            },
+            Repr::Heap(ref mut string) => string.push_str(s)
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<SoftAsciiChar> {
+        let ch = self.chars().next_back()?;
+        let new_len = self.len() - char::from(ch).len_utf8();
+        self.truncate(new_len);
+        Some(ch)
+    }
+
+    /// shortens the string to `new_len` bytes, does nothing if it's shorter
+    ///
+    /// # Panics
+    ///
+    /// if `new_len` is not on a char boundary
+    pub fn truncate(&mut self, new_len: usize) {
+        match self.repr {
+            Repr::Inline { ref mut len, ref buf } => {
+                if new_len < *len as usize {
+                    // Safe: see the comment on `Repr::Inline`
+                    let as_str = unsafe { str::from_utf8_unchecked(&buf[..*len as usize]) };
+                    assert!(as_str.is_char_boundary(new_len), "new_len is not on a char boundary");
+                    *len = new_len as u8;
+                }
+            },
+            Repr::Heap(ref mut string) => string.truncate(new_len)
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    #[inline]
+    pub fn as_soft_ascii_str(&self) -> &SoftAsciiStr {
+        match self.repr {
+            Repr::Inline { len, ref buf } => {
+                // Safe: see the comment on `Repr::Inline`
+                SoftAsciiStr::rewrap(unsafe { str::from_utf8_unchecked(&buf[..len as usize]) })
+            },
+            Repr::Heap(ref string) => string
+        }
+    }
+
+    #[inline]
+    pub fn as_mut_soft_ascii_str(&mut self) -> &mut SoftAsciiStr {
+        match self.repr {
+            Repr::Inline { len, ref mut buf } => {
+                // Safe: see the comment on `Repr::Inline`
+                SoftAsciiStr::rewrap_mut(unsafe { str::from_utf8_unchecked_mut(&mut buf[..len as usize]) })
+            },
+            Repr::Heap(ref mut string) => string
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.as_soft_ascii_str().as_str()
+    }
+
+    /// converts it into a `SoftAsciiString`, which only allocates if it's inline
+    pub fn into_soft_ascii_string(self) -> SoftAsciiString {
+        match self.repr {
+            Repr::Inline { .. } => self.as_soft_ascii_str().to_owned(),
+            Repr::Heap(string) => string
+        }
+    }
+}
+
+impl Default for SoftAsciiSmallString {
+    #[inline]
+    fn default() -> Self {
+        SoftAsciiSmallString::new()
+    }
+}
+
+impl Deref for SoftAsciiSmallString {
+    type Target = SoftAsciiStr;
+
+    #[inline]
+    fn deref(&self) -> &SoftAsciiStr {
+        self.as_soft_ascii_str()
+    }
+}
+
+impl DerefMut for SoftAsciiSmallString {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut SoftAsciiStr {
+        self.as_mut_soft_ascii_str()
+    }
+}
+
+impl_soft_ascii_str_traits!([] SoftAsciiSmallString);
+
+impl PartialEq<SoftAsciiString> for SoftAsciiSmallString {
+    #[inline]
+    fn eq(&self, other: &SoftAsciiString) -> bool {
+        self.as_soft_ascii_str() == &**other
+    }
+}
+
+impl<'a> From<&'a SoftAsciiStr> for SoftAsciiSmallString {
+    fn from(s: &'a SoftAsciiStr) -> Self {
+        if s.len() <= INLINE_CAPACITY {
+            let mut small = SoftAsciiSmallString::new();
+            small.push_str(s);
+            small
+        } else {
+            SoftAsciiSmallString { repr: Repr::Heap(s.to_owned()) }
+        }
+    }
+}
+
+/// keeps the heap buffer of `string`, nothing is copied
+impl From<SoftAsciiString> for SoftAsciiSmallString {
+    #[inline]
+    fn from(string: SoftAsciiString) -> Self {
+        SoftAsciiSmallString { repr: Repr::Heap(string) }
+    }
+}
+
+impl From<SoftAsciiSmallString> for SoftAsciiString {
+    #[inline]
+    fn from(small: SoftAsciiSmallString) -> Self {
+        small.into_soft_ascii_string()
+    }
+}
+
+impl FromIterator<SoftAsciiChar> for SoftAsciiSmallString {
+    fn from_iter<I: IntoIterator<Item=SoftAsciiChar>>(iter: I) -> Self {
+        let mut small = SoftAsciiSmallString::new();
+        small.extend(iter);
+        small
+    }
+}
+
+impl<'a> FromIterator<&'a SoftAsciiChar> for SoftAsciiSmallString {
+    fn from_iter<I: IntoIterator<Item=&'a SoftAsciiChar>>(iter: I) -> Self {
+        let mut small = SoftAsciiSmallString::new();
+        small.extend(iter);
+        small
+    }
+}
+
+impl<'a> FromIterator<&'a SoftAsciiStr> for SoftAsciiSmallString {
+    fn from_iter<I: IntoIterator<Item=&'a SoftAsciiStr>>(iter: I) -> Self {
+        let mut small = SoftAsciiSmallString::new();
+        small.extend(iter);
+        small
+    }
+}
+
+impl Extend<SoftAsciiChar> for SoftAsciiSmallString {
+    fn extend<I: IntoIterator<Item=SoftAsciiChar>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for ch in iter {
+            self.push(ch);
+        }
+    }
+}
+
+impl<'a> Extend<&'a SoftAsciiChar> for SoftAsciiSmallString {
+    fn extend<I: IntoIterator<Item=&'a SoftAsciiChar>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+impl<'a> Extend<&'a SoftAsciiStr> for SoftAsciiSmallString {
+    fn extend<I: IntoIterator<Item=&'a SoftAsciiStr>>(&mut self, iter: I) {
+        for s in iter {
+            self.push_str(s);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::mem;
+    use ::{SoftAsciiChar, SoftAsciiStr, SoftAsciiString};
+    use super::SoftAsciiSmallString;
+
+    #[test]
+    fn small_values_stay_inline() {
+        let mut small = SoftAsciiSmallString::new();
+        for _ in 0..SoftAsciiSmallString::INLINE_CAPACITY {
+            small.push(SoftAsciiChar::from_unchecked('a'));
+        }
+        assert!(small.is_inline());
+        assert_eq!(small.len(), SoftAsciiSmallString::INLINE_CAPACITY);
+        small.push(SoftAsciiChar::from_unchecked('b'));
+        assert!(!small.is_inline());
+        assert_eq!(small.len(), SoftAsciiSmallString::INLINE_CAPACITY + 1);
+        assert!(small.ends_with("ab"));
+    }
+
+    #[test]
+    fn spills_on_long_push_str() {
+        let mut small = SoftAsciiSmallString::from_unchecked("Content-Type");
+        assert!(small.is_inline());
+        small.push_str(SoftAsciiStr::from_unchecked(": text/plain; charset=utf-8"));
+        assert!(!small.is_inline());
+        assert_eq!(small, "Content-Type: text/plain; charset=utf-8");
+        small.truncate(12);
+        assert_eq!(small, "Content-Type");
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_overflow() {
+        let mut small = SoftAsciiSmallString::from_unchecked("abc");
+        small.reserve(usize::MAX);
+    }
+
+    #[test]
+    fn pop_truncate_clear() {
+        let mut small = SoftAsciiSmallString::from_unchecked("hello");
+        assert_eq!(small.pop(), Some(SoftAsciiChar::from_unchecked('o')));
+        small.truncate(2);
+        assert_eq!(small, "he");
+        small.truncate(10);
+        assert_eq!(small, "he");
+        small.clear();
+        assert!(small.is_empty());
+        assert_eq!(small.pop(), None);
+    }
+
+    #[test]
+    fn keeps_utf8_if_soft_constraint_is_broken() {
+        // `rewrap` isn't checked with `check-unchecked`
+        let mut small = SoftAsciiSmallString::from(SoftAsciiStr::rewrap("a↓"));
+        assert_eq!(small.pop(), Some(SoftAsciiChar::rewrap('↓')));
+        small.push(SoftAsciiChar::rewrap('↓'));
+        assert_eq!(small, "a↓");
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let chars = [SoftAsciiChar::from_unchecked('a'), SoftAsciiChar::from_unchecked('b')];
+        let small: SoftAsciiSmallString = chars.iter().collect();
+        assert_eq!(small, "ab");
+        let mut small: SoftAsciiSmallString = vec![
+            SoftAsciiStr::from_unchecked("text"),
+            SoftAsciiStr::from_unchecked("/")
+        ].into_iter().collect();
+        small.extend(SoftAsciiStr::from_unchecked("plain").chars());
+        assert!(small.is_inline());
+        assert_eq!(small, "text/plain");
+    }
+
+    #[test]
+    fn conversions_are_lossless() {
+        let string = SoftAsciiString::from_unchecked("a long value which is not inline");
+        let ptr = string.as_ptr();
+        let small = SoftAsciiSmallString::from(string);
+        assert_eq!(small.as_ptr(), ptr);
+        let string = SoftAsciiString::from(small);
+        assert_eq!(string.as_ptr(), ptr);
+
+        let small = SoftAsciiSmallString::from_unchecked("short");
+        let string: SoftAsciiString = small.clone().into();
+        assert_eq!(string, "short");
+        assert_eq!(small, string);
+    }
+
+    #[test]
+    fn eq_hash_ord_like_soft_ascii_str() {
+        let inline = SoftAsciiSmallString::from_unchecked("abc");
+        let heap = SoftAsciiSmallString::from(SoftAsciiString::from_unchecked("abc"));
+        assert_eq!(inline, heap);
+        assert!(inline < SoftAsciiSmallString::from_unchecked("abd"));
+        let mut set = HashSet::new();
+        set.insert(heap);
+        assert!(set.contains(&inline));
+        assert!(set.contains(SoftAsciiStr::from_unchecked("abc")));
+        assert_eq!(format!("{} {:?}", inline, inline), "abc \"abc\"");
+    }
+
+    #[test]
+    fn not_much_larger_than_soft_ascii_string() {
+        assert!(mem::size_of::<SoftAsciiSmallString>() <= mem::size_of::<SoftAsciiString>() + 8);
+    }
+}
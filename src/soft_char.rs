@@ -1,15 +1,19 @@
-use std::cmp::PartialEq;
-use std::fmt::{self, Display};
-use std::str;
-use std::char::{
+use core::cmp::PartialEq;
+use core::fmt::{self, Display};
+use core::str;
+use core::char::{
     self,
     ToUppercase, ToLowercase,
     EscapeDebug, EscapeDefault, EscapeUnicode
 };
+#[cfg(feature = "std")]
 #[allow(unused_imports, deprecated)]
 use std::ascii::AsciiExt;
 
+#[cfg(feature = "check-unchecked")]
+use check::{self, Origin};
 use error::FromSourceError;
+use violation::{self, ValueType, Trigger};
 
 
 ///a `char` wrapper with a "is us-ascii" soft constraint
@@ -22,12 +26,47 @@ pub struct SoftAsciiChar(char);
 
 impl SoftAsciiChar {
 
+    /// the char (`'?'`) used by the lossy constructors to replace non us-ascii chars
+    pub const REPLACEMENT_CHARACTER: SoftAsciiChar = SoftAsciiChar('?');
+
+    /// wraps `ch` without checking that it is us-ascii
+    ///
+    /// Unlike the unchecked constructors of `SoftAsciiStr`/`SoftAsciiString`
+    /// this is not tracked by the `track-unchecked` feature, a `SoftAsciiChar`
+    /// is a `Copy` value without an address to key the side table by. So a
+    /// `FromSourceError` from `revalidate_soft_constraint` never has a
+    /// `created_at` location for it.
     #[inline(always)]
+    #[cfg_attr(feature = "check-unchecked", track_caller)]
     pub fn from_unchecked(ch: char) -> Self {
+        #[cfg(feature = "check-unchecked")]
+        check::check(Origin::SoftAsciiChar, ch.encode_utf8(&mut [0u8; 4]));
         SoftAsciiChar(ch)
     }
 
+    /// creates a `SoftAsciiChar` in a const context, checking that `ch` is us-ascii
+    ///
+    /// # Panics
+    ///
+    /// If `ch` is not us-ascii. When evaluated at compile time (e.g. in a
+    /// `const` item or through `soft_ascii_char!`) this is a compile error.
+    #[inline]
+    pub const fn from_char_const(ch: char) -> Self {
+        if !ch.is_ascii() {
+            panic!("non us-ascii char passed to SoftAsciiChar::from_char_const");
+        }
+        SoftAsciiChar(ch)
+    }
+
+    /// wraps a char known to be ascii (e.g. taken from an existing soft value),
+    /// it's never re-checked or tracked
     #[inline(always)]
+    pub(crate) fn rewrap(ch: char) -> Self {
+        SoftAsciiChar(ch)
+    }
+
+    #[inline(always)]
+    #[cfg_attr(feature = "check-unchecked", track_caller)]
     #[deprecated(since="1.0.0", note="use from_unchecked")]
     pub fn from_char_unchecked(ch: char) -> Self {
         SoftAsciiChar::from_unchecked(ch)
@@ -37,7 +76,7 @@ impl SoftAsciiChar {
         if ch.is_ascii() {
             Ok(SoftAsciiChar(ch))
         } else {
-            Err(FromSourceError::new(ch))
+            Err(FromSourceError::from_source(ch))
         }
     }
 
@@ -46,10 +85,12 @@ impl SoftAsciiChar {
         self.0.is_ascii()
     }
 
+    #[cfg_attr(debug_assertions, track_caller)]
     pub fn revalidate_soft_constraint(self) -> Result<Self, char> {
         if self.is_ascii() {
             Ok(self)
         } else {
+            violation::report(ValueType::SoftAsciiChar, Trigger::Revalidate, self.0, 0);
             Err(self.0)
         }
     }
@@ -62,9 +103,9 @@ impl Display for SoftAsciiChar {
     }
 }
 
-impl Into<char> for SoftAsciiChar {
-    fn into(self) -> char {
-        self.0
+impl From<SoftAsciiChar> for char {
+    fn from(ch: SoftAsciiChar) -> char {
+        ch.0
     }
 }
 
@@ -132,6 +173,21 @@ mod test {
             assert_eq!(res, FromSourceError::new('↓'));
         }
 
+        #[test]
+        fn from_char_const() {
+            const COLON: SoftAsciiChar = SoftAsciiChar::from_char_const(':');
+            assert_eq!(COLON, ':');
+            assert_eq!(soft_ascii_char!('\x7f'), '\x7f');
+        }
+
+        #[test]
+        #[should_panic]
+        fn from_char_const_non_ascii_at_runtime() {
+            let ch = '↓';
+            let _ = SoftAsciiChar::from_char_const(ch);
+        }
+
+        #[cfg(not(feature = "check-unchecked"))]
         #[test]
         fn from_unchecked() {
             let a: SoftAsciiChar = SoftAsciiChar::from_unchecked('a');
@@ -145,7 +201,8 @@ mod test {
             let a: SoftAsciiChar = SoftAsciiChar::from_unchecked('a');
             let val = assert_ok!(a.revalidate_soft_constraint());
             assert_eq!(val, 'a');
-            let a: SoftAsciiChar = SoftAsciiChar::from_unchecked('↓');
+            // `rewrap` isn't checked with `check-unchecked`
+            let a: SoftAsciiChar = SoftAsciiChar::rewrap('↓');
             let val = assert_err!(a.revalidate_soft_constraint());
             assert_eq!(val, '↓');
         }
@@ -1,27 +1,55 @@
-use std::ops::{
-    Index,  Range,
+use core::ops::{
+    Index, IndexMut, Range,
     RangeFrom, RangeTo,
-    RangeFull,
+    RangeFull, RangeInclusive,
+    RangeToInclusive
 };
-use std::cmp::PartialEq;
-use std::default::Default;
-use std::fmt::{self, Display};
-use std::borrow::{ToOwned, Cow};
+#[cfg(feature = "check-unchecked")]
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "check-unchecked")]
+use core::panic::Location;
+use core::cmp::PartialEq;
+use core::default::Default;
+use core::fmt::{self, Display};
+use core::str::{self, FromStr, EncodeUtf16};
+use core::iter::{Iterator, DoubleEndedIterator};
+#[cfg(feature = "alloc")]
+use alloc::borrow::{ToOwned, Cow};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "std")]
 use std::ffi::{OsString, OsStr};
+#[cfg(feature = "std")]
 use std::path::Path;
+#[cfg(feature = "std")]
 use std::net::{ToSocketAddrs, SocketAddr};
-use std::str::{self, FromStr, EncodeUtf16};
+#[cfg(feature = "std")]
 use std::{vec, io};
-use std::iter::{Iterator, DoubleEndedIterator};
 
 // this import will become unused in future rust versions
 // but won't be removed for now for supporting current
 // rust versions
-#[allow(warnings)]
+#[cfg(feature = "std")]
+#[allow(unused_imports, deprecated)]
 use std::ascii::AsciiExt;
 
-use error::FromSourceError;
+use case;
+#[cfg(feature = "check-unchecked")]
+use check::{self, Origin};
+use error::{FromSourceError, Violation};
+use pattern::{
+    Pattern, SoftAsciiSplit, SoftAsciiRSplit, SoftAsciiSplitN, SoftAsciiSplitTerminator,
+    SoftAsciiMatches, SoftAsciiMatchIndices
+};
 use soft_char::SoftAsciiChar;
+#[cfg(feature = "track-unchecked")]
+use track;
+#[cfg(feature = "alloc")]
+use validate;
+use violation::{self, ValueType, Trigger};
+#[cfg(feature = "alloc")]
 use soft_string::SoftAsciiString;
 
 /// A `str` wrapper adding a "is us-ascii" soft constraint.
@@ -37,13 +65,9 @@ use soft_string::SoftAsciiString;
 ///
 ///
 /// # Note
-/// Some functions which should be implemented directly
-/// on `SoftAsciiStr` like e.g. `trim_matches` are only
-/// provided through `.as_str()`. This
-/// is because the Pattern API and SliceIndex API is unstable
-/// i.e. can only be implemented in unstable for now.
-/// Once it gets stabilized (rust #27721/#35729) implementations
-/// can be added
+/// As the std Pattern API is unstable (rust #27721) search and
+/// split methods like `split` or `trim_matches` use the crate
+/// local `pattern::Pattern` trait instead.
 #[derive(Debug,  PartialEq, Eq, PartialOrd, Ord, Hash)]
 // `repr(transparent)` ensures that the internal layout of
 // `SoftAsciiStr` is same as that of `str`.
@@ -56,35 +80,100 @@ pub struct SoftAsciiStr(str);
 impl SoftAsciiStr {
 
     #[inline(always)]
+    #[cfg_attr(any(feature = "check-unchecked", feature = "track-unchecked", debug_assertions), track_caller)]
     pub fn from_unchecked(s: &str) -> &SoftAsciiStr {
-        unsafe { &*( s as *const str as *const SoftAsciiStr) }
+        #[cfg(feature = "check-unchecked")]
+        check::check(Origin::SoftAsciiStr, s);
+        violation::record_creation(s);
+        SoftAsciiStr::rewrap(s)
     }
 
     #[inline(always)]
+    #[cfg_attr(any(feature = "check-unchecked", feature = "track-unchecked", debug_assertions), track_caller)]
     #[deprecated(since = "1.0.0", note="use from_unchecked")]
     pub fn from_str_unchecked(s: &str) -> &SoftAsciiStr {
         SoftAsciiStr::from_unchecked(s)
     }
 
     #[inline(always)]
+    #[cfg_attr(any(feature = "check-unchecked", feature = "track-unchecked", debug_assertions), track_caller)]
     pub fn from_unchecked_mut(s: &mut str) -> &mut SoftAsciiStr {
+        #[cfg(feature = "check-unchecked")]
+        check::check(Origin::SoftAsciiStr, s);
+        violation::record_creation(s);
+        SoftAsciiStr::rewrap_mut(s)
+    }
+
+    /// creates a `SoftAsciiStr` in a const context, checking that `s` is us-ascii
+    ///
+    /// # Panics
+    ///
+    /// If `s` contains non us-ascii chars. When evaluated at compile time
+    /// (e.g. in a `const` item or through `soft_ascii!`) this is a compile error.
+    #[inline]
+    pub const fn from_str_const(s: &str) -> &SoftAsciiStr {
+        if !s.is_ascii() {
+            panic!("non us-ascii str passed to SoftAsciiStr::from_str_const");
+        }
+        unsafe { &*(s as *const str as *const SoftAsciiStr) }
+    }
+
+    /// wraps a `str` known to be ascii (e.g. derived from an existing soft value
+    /// or an internal constant), it's never re-checked or tracked
+    #[inline(always)]
+    pub(crate) fn rewrap(s: &str) -> &SoftAsciiStr {
+        unsafe { &*( s as *const str as *const SoftAsciiStr) }
+    }
+
+    /// wraps a `str` derived from an existing soft value, it's never re-checked
+    #[inline(always)]
+    pub(crate) fn rewrap_mut(s: &mut str) -> &mut SoftAsciiStr {
         unsafe { &mut *( s as *mut str as *mut SoftAsciiStr) }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(source: &str) -> Result<&Self, FromSourceError<&str>> {
-        if source.is_ascii() {
-            Ok(Self::from_unchecked(source))
-        } else {
-            Err(FromSourceError::new(source))
+        match Violation::find(source) {
+            None => Ok(Self::rewrap(source)),
+            Some(violation) => Err(FromSourceError::from_violation(source, violation))
+        }
+    }
+
+    /// creates a `SoftAsciiStr` replacing all non us-ascii chars
+    ///
+    /// If `source` is us-ascii it's borrowed, else a `SoftAsciiString`
+    /// is created in which each non us-ascii char is replaced by
+    /// `SoftAsciiChar::REPLACEMENT_CHARACTER`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn from_str_lossy(source: &str) -> Cow<'_, SoftAsciiStr> {
+        match validate::first_non_ascii(source.as_bytes()) {
+            None => Cow::Borrowed(SoftAsciiStr::rewrap(source)),
+            Some(valid_up_to) => {
+                violation::report_in(ValueType::SoftAsciiStr, Trigger::Lossy, source, valid_up_to);
+                let mut buf = SoftAsciiString::with_capacity(source.len());
+                buf.push_lossy(source, SoftAsciiChar::REPLACEMENT_CHARACTER);
+                Cow::Owned(buf)
+            }
         }
     }
 
     /// reruns checks if the "is us-ascii" soft constraint is still valid
+    ///
+    /// A violation is reported to the `violation` module.
+    #[cfg_attr(debug_assertions, track_caller)]
     pub fn revalidate_soft_constraint(&self) -> Result<&Self, FromSourceError<&str>> {
-        if self.is_ascii() {
-            Ok(self)
-        } else {
-            Err(FromSourceError::new(self.as_str()))
+        match Violation::find(self.as_str()) {
+            None => Ok(self),
+            Some(violation) => {
+                let valid_up_to = violation.valid_up_to();
+                let error = FromSourceError::from_violation(self.as_str(), violation);
+                #[cfg(feature = "track-unchecked")]
+                let error = error.with_created_at(track::lookup(self.as_str(), valid_up_to));
+                violation::report_in(ValueType::SoftAsciiStr, Trigger::Revalidate,
+                    self.as_str(), valid_up_to);
+                Err(error)
+            }
         }
     }
 
@@ -93,52 +182,60 @@ impl SoftAsciiStr {
         &self.0
     }
 
+    #[cfg(feature = "alloc")]
     pub fn into_soft_ascii_string(self: Box<SoftAsciiStr>) -> SoftAsciiString {
         //Box<SoftAsciiStr> -> Box<str> -> String -> SoftAsciiString
         //Safe: basicaly coerces Box<SoftAsciiStr> to Box<str>
         let as_str = Self::into_boxed_str(self);
         let string = str::into_string(as_str);
-        SoftAsciiString::from_unchecked(string)
+        SoftAsciiString::rewrap(string)
     }
 
+    #[cfg(feature = "alloc")]
     pub fn from_boxed_str(bs: Box<str>) -> Box<SoftAsciiStr> {
         unsafe { Box::from_raw(Box::into_raw(bs) as *mut SoftAsciiStr) }
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     pub fn into_boxed_str(self: Box<SoftAsciiStr>) -> Box<str> {
         unsafe { Box::from_raw(Box::into_raw(self) as *mut str) }
     }
 
     #[inline]
-    pub fn lines(&self) -> SoftAsciiLines {
+    pub fn lines(&self) -> SoftAsciiLines<'_> {
         SoftAsciiLines::from(self)
     }
 
     #[inline]
-    pub fn split_whitespace(&self) -> SoftAsciiSplitWhitespace {
+    pub fn split_whitespace(&self) -> SoftAsciiSplitWhitespace<'_> {
         SoftAsciiSplitWhitespace::from(self)
     }
 
     #[inline]
-    pub fn char_indices(&self) -> SoftAsciiCharIndices {
+    pub fn char_indices(&self) -> SoftAsciiCharIndices<'_> {
         SoftAsciiCharIndices::from(self)
     }
 
     #[inline]
-    pub fn chars(&self) -> SoftAsciiChars {
+    pub fn chars(&self) -> SoftAsciiChars<'_> {
         SoftAsciiChars::from(self)
     }
 
     pub fn split_at(&self, mid: usize) -> (&SoftAsciiStr, &SoftAsciiStr) {
         let (left, right) = self.as_str().split_at(mid);
-        (SoftAsciiStr::from_unchecked(left), SoftAsciiStr::from_unchecked(right))
+        (SoftAsciiStr::rewrap(left), SoftAsciiStr::rewrap(right))
     }
 
+    /// # Safety
+    ///
+    /// same as for [`std::str::slice_unchecked`]
+    ///
+    /// [`std::str::slice_unchecked`]: https://doc.rust-lang.org/std/primitive.str.html#method.slice_unchecked
     #[deprecated(since="1.1.0", note="deprecated in std")]
     pub unsafe fn slice_unchecked(&self, begin: usize, end: usize) -> &SoftAsciiStr {
         #[allow(deprecated)]
-        SoftAsciiStr::from_unchecked(self.as_str().slice_unchecked(begin, end))
+        SoftAsciiStr::rewrap(self.as_str().slice_unchecked(begin, end))
     }
 
     /// Proxy of [`std::str::get_unchecked`].
@@ -156,12 +253,54 @@ impl SoftAsciiStr {
     /// can be implemented using `SliceIndex<SoftAsciiStr>`
     /// bounds.
     ///
+    /// # Safety
+    ///
+    /// same as for [`std::str::get_unchecked`]
+    ///
     /// [`std::str::get_unchecked`]: https://doc.rust-lang.org/std/primitive.str.html#method.get_unchecked
     pub unsafe fn get_unchecked<I>(&self, index: I) -> &SoftAsciiStr
     where
         I: hidden::TempSliceIndexHelper
     {
-        SoftAsciiStr::from_unchecked(self.as_str().get_unchecked::<I>(index))
+        SoftAsciiStr::rewrap(self.as_str().get_unchecked::<I>(index))
+    }
+
+    /// returns the sub string for the given byte range
+    ///
+    /// Returns `None` if the range is out of bounds or its start or end
+    /// is not on a char boundary, the same range types as for
+    /// `get_unchecked` are supported.
+    #[inline]
+    pub fn get<I>(&self, index: I) -> Option<&SoftAsciiStr>
+        where I: hidden::TempSliceIndexHelper
+    {
+        self.as_str().get(index).map(SoftAsciiStr::rewrap)
+    }
+
+    /// mutable version of `get`
+    #[inline]
+    pub fn get_mut<I>(&mut self, index: I) -> Option<&mut SoftAsciiStr>
+        where I: hidden::TempSliceIndexHelper
+    {
+        self.0.get_mut(index).map(SoftAsciiStr::rewrap_mut)
+    }
+
+    /// returns the char starting at byte offset `idx`
+    ///
+    /// As long as the soft constraint holds byte offsets are char offsets
+    /// and this is O(1). If it is broken `None` is returned if `idx` is
+    /// not on a char boundary, else the (non us-ascii) char starting at
+    /// `idx` is returned.
+    #[inline]
+    pub fn char_at(&self, idx: usize) -> Option<SoftAsciiChar> {
+        let byte = *self.as_bytes().get(idx)?;
+        if byte < 0x80 {
+            Some(SoftAsciiChar::rewrap(byte as char))
+        } else if self.is_char_boundary(idx) {
+            self.0[idx..].chars().next().map(SoftAsciiChar::rewrap)
+        } else {
+            None
+        }
     }
 
 
@@ -174,10 +313,32 @@ impl SoftAsciiStr {
     /// compared to a AsciiStr implementation this won't
     /// introduce unsafety, just possible brakeage of the
     /// soft constraint that the data should be ascii.
+    ///
+    /// Changes made through the returned reference are _not_ re-checked,
+    /// not even with the `check-unchecked` feature, use
+    /// `inner_str_mut_checked` for that.
     pub fn inner_str_mut(&mut self) -> &mut str {
         &mut self.0
     }
 
+    /// like `inner_str_mut` but the soft constraint is re-checked when the returned guard is dropped
+    ///
+    /// # Panics
+    ///
+    /// if the value is not us-ascii when the guard is dropped and the
+    /// `check::Policy` panics. With `std` the check is skipped if the guard
+    /// is dropped while the thread is already panicking (a second panic
+    /// would abort the process), without `std` this can't be detected.
+    #[cfg(feature = "check-unchecked")]
+    #[inline]
+    #[track_caller]
+    pub fn inner_str_mut_checked(&mut self) -> InnerStrMut<'_> {
+        InnerStrMut {
+            inner: &mut self.0,
+            location: Location::caller()
+        }
+    }
+
     pub fn parse<F>(&self) -> Result<F, <F as FromStr>::Err>
          where F: FromStr
     {
@@ -185,9 +346,208 @@ impl SoftAsciiStr {
     }
 }
 
+/// search and split methods, see the `pattern` module
+impl SoftAsciiStr {
+
+    /// returns an iterator over the parts of this string separated by `pat`
+    #[inline]
+    pub fn split<P: Pattern>(&self, pat: P) -> SoftAsciiSplit<'_, P> {
+        SoftAsciiSplit::new(self, pat)
+    }
+
+    /// returns an iterator over the parts of this string separated by `pat` in reverse order
+    #[inline]
+    pub fn rsplit<P: Pattern>(&self, pat: P) -> SoftAsciiRSplit<'_, P> {
+        SoftAsciiRSplit::new(self, pat)
+    }
+
+    /// like `split` but returns at most `count` parts, the last one containing the remainder
+    #[inline]
+    pub fn splitn<P: Pattern>(&self, count: usize, pat: P) -> SoftAsciiSplitN<'_, P> {
+        SoftAsciiSplitN::new(self, count, pat)
+    }
+
+    /// like `split` but skips a trailing empty part
+    #[inline]
+    pub fn split_terminator<P: Pattern>(&self, pat: P) -> SoftAsciiSplitTerminator<'_, P> {
+        SoftAsciiSplitTerminator::new(self, pat)
+    }
+
+    /// splits the string at the first match of `pat`, excluding the match
+    pub fn split_once<P: Pattern>(&self, mut pat: P) -> Option<(&SoftAsciiStr, &SoftAsciiStr)> {
+        let (start, end) = pat.find_in(self.as_str())?;
+        Some((SoftAsciiStr::rewrap(&self.0[..start]), SoftAsciiStr::rewrap(&self.0[end..])))
+    }
+
+    /// splits the string at the last match of `pat`, excluding the match
+    pub fn rsplit_once<P: Pattern>(&self, mut pat: P) -> Option<(&SoftAsciiStr, &SoftAsciiStr)> {
+        let (start, end) = pat.rfind_in(self.as_str())?;
+        Some((SoftAsciiStr::rewrap(&self.0[..start]), SoftAsciiStr::rewrap(&self.0[end..])))
+    }
+
+    /// returns an iterator over the (non overlapping) matches of `pat`
+    #[inline]
+    pub fn matches<P: Pattern>(&self, pat: P) -> SoftAsciiMatches<'_, P> {
+        SoftAsciiMatches::new(self, pat)
+    }
+
+    /// returns an iterator over the (non overlapping) matches of `pat` and their byte offsets
+    #[inline]
+    pub fn match_indices<P: Pattern>(&self, pat: P) -> SoftAsciiMatchIndices<'_, P> {
+        SoftAsciiMatchIndices::new(self, pat)
+    }
+
+    /// returns the byte offset of the first match of `pat`
+    #[inline]
+    pub fn find<P: Pattern>(&self, mut pat: P) -> Option<usize> {
+        pat.find_in(self.as_str()).map(|(start, _)| start)
+    }
+
+    /// returns the byte offset of the last match of `pat`
+    #[inline]
+    pub fn rfind<P: Pattern>(&self, mut pat: P) -> Option<usize> {
+        pat.rfind_in(self.as_str()).map(|(start, _)| start)
+    }
+
+    #[inline]
+    pub fn contains<P: Pattern>(&self, mut pat: P) -> bool {
+        pat.find_in(self.as_str()).is_some()
+    }
+
+    #[inline]
+    pub fn starts_with<P: Pattern>(&self, mut pat: P) -> bool {
+        pat.prefix_len_in(self.as_str()).is_some()
+    }
+
+    #[inline]
+    pub fn ends_with<P: Pattern>(&self, mut pat: P) -> bool {
+        pat.suffix_len_in(self.as_str()).is_some()
+    }
+
+    /// returns the string without the prefix matched by `pat`, `None` if it doesn't start with it
+    pub fn strip_prefix<P: Pattern>(&self, mut pat: P) -> Option<&SoftAsciiStr> {
+        pat.prefix_len_in(self.as_str())
+            .map(|len| SoftAsciiStr::rewrap(&self.0[len..]))
+    }
+
+    /// returns the string without the suffix matched by `pat`, `None` if it doesn't end with it
+    pub fn strip_suffix<P: Pattern>(&self, mut pat: P) -> Option<&SoftAsciiStr> {
+        pat.suffix_len_in(self.as_str())
+            .map(|len| SoftAsciiStr::rewrap(&self.0[..self.len() - len]))
+    }
+
+    /// removes all prefixes and suffixes matched by `pat`
+    ///
+    /// Unlike `str::trim_matches` this also accepts `&str` patterns.
+    pub fn trim_matches<P: Pattern>(&self, mut pat: P) -> &SoftAsciiStr {
+        let start = trim_start_len(self.as_str(), &mut pat);
+        let end = self.len() - trim_end_len(&self.0[start..], &mut pat);
+        SoftAsciiStr::rewrap(&self.0[start..end])
+    }
+
+    /// removes all prefixes matched by `pat`
+    pub fn trim_start_matches<P: Pattern>(&self, mut pat: P) -> &SoftAsciiStr {
+        let start = trim_start_len(self.as_str(), &mut pat);
+        SoftAsciiStr::rewrap(&self.0[start..])
+    }
+
+    /// removes all suffixes matched by `pat`
+    pub fn trim_end_matches<P: Pattern>(&self, mut pat: P) -> &SoftAsciiStr {
+        let end = self.len() - trim_end_len(self.as_str(), &mut pat);
+        SoftAsciiStr::rewrap(&self.0[..end])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SoftAsciiStr {
+
+    /// replaces all matches of `pat` with `to`
+    #[inline]
+    pub fn replace<P: Pattern>(&self, pat: P, to: &SoftAsciiStr) -> SoftAsciiString {
+        self.replacen(pat, to, usize::MAX)
+    }
+
+    /// replaces the first `count` matches of `pat` with `to`
+    pub fn replacen<P: Pattern>(&self, pat: P, to: &SoftAsciiStr, count: usize) -> SoftAsciiString {
+        let mut result = SoftAsciiString::with_capacity(self.len());
+        let mut last_end = 0;
+        for (start, part) in self.match_indices(pat).take(count) {
+            result.push_str(&self[last_end..start]);
+            result.push_str(to);
+            last_end = start + part.len();
+        }
+        result.push_str(&self[last_end..]);
+        result
+    }
+
+    /// returns a copy with `A`-`Z` mapped to `a`-`z`
+    pub fn to_ascii_lowercase(&self) -> SoftAsciiString {
+        let mut result = self.to_owned();
+        result.make_ascii_lowercase();
+        result
+    }
+
+    /// returns a copy with `a`-`z` mapped to `A`-`Z`
+    pub fn to_ascii_uppercase(&self) -> SoftAsciiString {
+        let mut result = self.to_owned();
+        result.make_ascii_uppercase();
+        result
+    }
+}
+
+impl SoftAsciiStr {
+
+    /// maps `A`-`Z` to `a`-`z` in place
+    ///
+    /// Other chars, including non us-ascii ones, are not changed.
+    pub fn make_ascii_lowercase(&mut self) {
+        // Safe: only us-ascii bytes are replaced with us-ascii bytes
+        case::make_lowercase(unsafe { self.0.as_bytes_mut() })
+    }
+
+    /// maps `a`-`z` to `A`-`Z` in place
+    ///
+    /// Other chars, including non us-ascii ones, are not changed.
+    pub fn make_ascii_uppercase(&mut self) {
+        // Safe: only us-ascii bytes are replaced with us-ascii bytes
+        case::make_uppercase(unsafe { self.0.as_bytes_mut() })
+    }
+
+    /// checks if `self` and `other` are equal ignoring the case of `A`-`Z`/`a`-`z`
+    ///
+    /// `other` can be a `str`, `SoftAsciiStr`, `String` or `SoftAsciiString`.
+    pub fn eq_ignore_ascii_case<S: AsRef<str> + ?Sized>(&self, other: &S) -> bool {
+        case::eq_ignore_case(self.as_bytes(), other.as_ref().as_bytes())
+    }
+}
+
+/// returns the length of the prefix consisting of matches of `pat`
+fn trim_start_len<P: Pattern>(s: &str, pat: &mut P) -> usize {
+    let mut start = 0;
+    while let Some(len) = pat.prefix_len_in(&s[start..]) {
+        if len == 0 {
+            break;
+        }
+        start += len;
+    }
+    start
+}
+
+/// returns the length of the suffix consisting of matches of `pat`
+fn trim_end_len<P: Pattern>(s: &str, pat: &mut P) -> usize {
+    let mut end = s.len();
+    while let Some(len) = pat.suffix_len_in(&s[..end]) {
+        if len == 0 {
+            break;
+        }
+        end -= len;
+    }
+    s.len() - end
+}
+
 mod hidden {
-    use std::slice::SliceIndex;
-    use std::ops::{Range, RangeFrom, RangeTo, RangeFull, RangeToInclusive, RangeInclusive};
+    use core::slice::SliceIndex;
+    use core::ops::{Range, RangeFrom, RangeTo, RangeFull, RangeToInclusive, RangeInclusive};
 
     /// This is a workaround to be able to provide `get_unchecked` by now on stable.
     ///
@@ -198,6 +558,9 @@ mod hidden {
     ///
     /// So instead we add this "helper" trait and prevent custom implementations of
     /// it by not re-exporting it.
+    ///
+    /// Note that even with the `nightly` feature `SliceIndex<SoftAsciiStr>` can't
+    /// be implemented, as `SliceIndex` can only be implemented inside of `core`.
     //NIT[rustc/sealed]: When/if rust provides a mechanism for sealed traits use that instead.
     pub trait TempSliceIndexHelper: SliceIndex<str, Output=str> {}
 
@@ -210,16 +573,51 @@ mod hidden {
 
 }
 
+/// mutable access to the `str` of a `SoftAsciiStr`, returned by `SoftAsciiStr::inner_str_mut_checked`
+#[cfg(feature = "check-unchecked")]
+#[derive(Debug)]
+pub struct InnerStrMut<'a> {
+    inner: &'a mut str,
+    location: &'static Location<'static>
+}
+
+#[cfg(feature = "check-unchecked")]
+impl<'a> Deref for InnerStrMut<'a> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.inner
+    }
+}
+
+#[cfg(feature = "check-unchecked")]
+impl<'a> DerefMut for InnerStrMut<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut str {
+        self.inner
+    }
+}
+
+#[cfg(feature = "check-unchecked")]
+impl<'a> Drop for InnerStrMut<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        check::check_on_drop(Origin::InnerStrMut, self.inner, self.location);
+    }
+}
+
 //TODO FromStr with custom error
 
 macro_rules! impl_wrap_returning_string {
     (pub > $(fn $name:ident(&self$(, $param:ident: $tp:ty)*)),*) => ($(
+        #[cfg(feature = "alloc")]
         impl SoftAsciiStr {
             #[inline]
             pub fn $name(&self $(, $param: $tp)*) -> SoftAsciiString {
                 let as_str = self.as_str();
                 let res = str::$name(as_str $(, $param)*);
-                SoftAsciiString::from_unchecked(res)
+                SoftAsciiString::rewrap(res)
             }
         }
     )*)
@@ -245,7 +643,7 @@ macro_rules! impl_wrap_returning_str {
                 let as_str = self.as_str();
                 $(#[$inner_attr])* {
                     let res = str::$name(as_str $(, $param)*);
-                    SoftAsciiStr::from_unchecked(res)
+                    SoftAsciiStr::rewrap(res)
                 }
             }
         )*}
@@ -280,7 +678,7 @@ impl_wrapping! {
     fn is_empty(&self) -> bool,
     fn is_char_boundary(&self, index: usize) -> bool,
     fn as_ptr(&self) -> *const u8,
-    fn encode_utf16(&self) -> EncodeUtf16,
+    fn encode_utf16(&self) -> EncodeUtf16<'_>,
     fn is_ascii(&self) -> bool,
     fn as_bytes(&self) -> &[u8]
 }
@@ -308,7 +706,7 @@ impl AsRef<[u8]> for SoftAsciiStr {
 impl<'a> Default for &'a SoftAsciiStr {
     #[inline]
     fn default() -> &'a SoftAsciiStr {
-        SoftAsciiStr::from_unchecked(Default::default())
+        SoftAsciiStr::rewrap(Default::default())
     }
 }
 
@@ -323,7 +721,7 @@ macro_rules! impl_index {
         impl Index<$idx> for SoftAsciiStr {
             type Output = SoftAsciiStr;
             fn index(&self, index: $idx) -> &Self::Output {
-                SoftAsciiStr::from_unchecked(self.0.index(index))
+                SoftAsciiStr::rewrap(self.0.index(index))
             }
         }
     )*);
@@ -333,54 +731,82 @@ impl_index! {
     Range<usize>,
     RangeFrom<usize>,
     RangeTo<usize>,
-    RangeFull
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>
 }
 
+macro_rules! impl_index_mut {
+    ($($idx:ty),*) => ($(
+        impl IndexMut<$idx> for SoftAsciiStr {
+            fn index_mut(&mut self, index: $idx) -> &mut Self::Output {
+                SoftAsciiStr::rewrap_mut(self.0.index_mut(index))
+            }
+        }
+    )*);
+}
+
+impl_index_mut! {
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeTo<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>
+}
+
+#[cfg(feature = "alloc")]
 impl ToOwned for SoftAsciiStr {
     type Owned = SoftAsciiString;
 
     fn to_owned(&self) -> SoftAsciiString {
-        SoftAsciiString::from_unchecked(String::from(&self.0))
+        SoftAsciiString::rewrap(String::from(&self.0))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl PartialEq<SoftAsciiString> for SoftAsciiStr {
     fn eq(&self, other: &SoftAsciiString) -> bool {
         self == &**other
     }
 }
 
-impl<'a> PartialEq<SoftAsciiString> for &'a SoftAsciiStr {
+#[cfg(feature = "alloc")]
+impl PartialEq<SoftAsciiString> for &SoftAsciiStr {
     fn eq(&self, other: &SoftAsciiString) -> bool {
         *self == &**other
     }
 }
 
+#[cfg(feature = "alloc")]
 impl PartialEq<SoftAsciiStr> for String {
     fn eq(&self, other: &SoftAsciiStr) -> bool {
         self.as_str() == other.as_str()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl PartialEq<String> for SoftAsciiStr {
     fn eq(&self, other: &String) -> bool {
         self.as_str() == other.as_str()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> PartialEq<&'a SoftAsciiStr> for String {
     fn eq(&self, other: &&'a SoftAsciiStr) -> bool {
         self.as_str() == other.as_str()
     }
 }
 
-impl<'a> PartialEq<String> for &'a SoftAsciiStr {
+#[cfg(feature = "alloc")]
+impl PartialEq<String> for &SoftAsciiStr {
     fn eq(&self, other: &String) -> bool {
         self.as_str() == other.as_str()
     }
 }
 
-impl<'a> PartialEq<SoftAsciiStr> for str {
+impl PartialEq<SoftAsciiStr> for str {
     fn eq(&self, other: &SoftAsciiStr) -> bool {
         self == other.as_str()
     }
@@ -392,101 +818,118 @@ impl PartialEq<str> for SoftAsciiStr {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> PartialEq<SoftAsciiStr> for Cow<'a, SoftAsciiStr> {
     fn eq(&self, other: &SoftAsciiStr) -> bool {
         self.as_str() == other.as_str()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> PartialEq<Cow<'a, SoftAsciiStr>> for SoftAsciiStr {
     fn eq(&self, other: &Cow<'a, SoftAsciiStr>) -> bool {
         self.as_str() == other.as_str()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, 'b> PartialEq<&'b SoftAsciiStr> for Cow<'a, SoftAsciiStr> {
     fn eq(&self, other: &&'b SoftAsciiStr) -> bool {
         self.as_str() == other.as_str()
     }
 }
 
-impl<'a, 'b> PartialEq<Cow<'a, SoftAsciiStr>> for &'a SoftAsciiStr {
+#[cfg(feature = "alloc")]
+impl<'a> PartialEq<Cow<'a, SoftAsciiStr>> for &'a SoftAsciiStr {
     fn eq(&self, other: &Cow<'a, SoftAsciiStr>) -> bool {
         self.as_str() == other.as_str()
     }
 }
 
-impl<'a> PartialEq<SoftAsciiStr> for Cow<'a, str> {
+#[cfg(feature = "alloc")]
+impl PartialEq<SoftAsciiStr> for Cow<'_, str> {
     fn eq(&self, other: &SoftAsciiStr) -> bool {
-        &*self == other.as_str()
+        **self == *other.as_str()
     }
 }
 
-impl<'a> PartialEq<Cow<'a, str>> for SoftAsciiStr {
-    fn eq(&self, other: &Cow<'a, str>) -> bool {
-        self.as_str() == &*other
+#[cfg(feature = "alloc")]
+impl PartialEq<Cow<'_, str>> for SoftAsciiStr {
+    fn eq(&self, other: &Cow<'_, str>) -> bool {
+        *self.as_str() == **other
     }
 }
 
-impl<'a, 'b> PartialEq<&'b SoftAsciiStr> for Cow<'a, str> {
+#[cfg(feature = "alloc")]
+impl<'b> PartialEq<&'b SoftAsciiStr> for Cow<'_, str> {
     fn eq(&self, other: &&'b SoftAsciiStr) -> bool {
-        &*self == other.as_str()
+        **self == *other.as_str()
     }
 }
 
-impl<'a, 'b> PartialEq<Cow<'b, str>> for &'a SoftAsciiStr {
+#[cfg(feature = "alloc")]
+impl<'b> PartialEq<Cow<'b, str>> for &SoftAsciiStr {
     fn eq(&self, other: &Cow<'b, str>) -> bool {
-        self.as_str() == &*other
+        *self.as_str() == **other
     }
 }
 
+#[cfg(feature = "std")]
 impl PartialEq<SoftAsciiStr> for OsString {
     fn eq(&self, other: &SoftAsciiStr) -> bool {
         other.as_str().eq(self)
     }
 }
 
+#[cfg(feature = "std")]
 impl PartialEq<OsString> for SoftAsciiStr {
     fn eq(&self, other: &OsString) -> bool {
         self.as_str().eq(other)
     }
 }
 
+#[cfg(feature = "std")]
 impl<'a> PartialEq<&'a SoftAsciiStr> for OsString {
     fn eq(&self, other: &&'a SoftAsciiStr) -> bool {
         other.as_str().eq(self)
     }
 }
 
-impl<'a> PartialEq<OsString> for &'a SoftAsciiStr {
+#[cfg(feature = "std")]
+impl PartialEq<OsString> for &SoftAsciiStr {
     fn eq(&self, other: &OsString) -> bool {
         self.as_str().eq(other)
     }
 }
 
+#[cfg(feature = "std")]
 impl PartialEq<SoftAsciiStr> for OsStr {
     fn eq(&self, other: &SoftAsciiStr) -> bool {
         other.as_str().eq(self)
     }
 }
+#[cfg(feature = "std")]
 impl PartialEq<OsStr> for SoftAsciiStr {
     fn eq(&self, other: &OsStr) -> bool {
         self.as_str().eq(other)
     }
 }
 
+#[cfg(feature = "std")]
 impl AsRef<OsStr> for SoftAsciiStr {
     fn as_ref(&self) -> &OsStr {
         self.as_str().as_ref()
     }
 }
 
+#[cfg(feature = "std")]
 impl AsRef<Path> for SoftAsciiStr {
     fn as_ref(&self) -> &Path {
         self.as_str().as_ref()
     }
 }
 
+#[cfg(feature = "std")]
 impl ToSocketAddrs for SoftAsciiStr {
     type Iter = vec::IntoIter<SocketAddr>;
 
@@ -517,7 +960,7 @@ impl<'a> Iterator for SoftAsciiChars<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next()
-            .map(SoftAsciiChar::from_unchecked)
+            .map(SoftAsciiChar::rewrap)
 
     }
 
@@ -533,14 +976,14 @@ impl<'a> Iterator for SoftAsciiChars<'a> {
 
     fn last(self) -> Option<Self::Item> {
         self.inner.last()
-            .map(SoftAsciiChar::from_unchecked)
+            .map(SoftAsciiChar::rewrap)
     }
 }
 
 impl<'a> DoubleEndedIterator for SoftAsciiChars<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.inner.next_back()
-            .map(SoftAsciiChar::from_unchecked)
+            .map(SoftAsciiChar::rewrap)
     }
 }
 
@@ -567,7 +1010,7 @@ impl<'a> Iterator for SoftAsciiCharIndices<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next()
             .map(|(idx, ch)| {
-                (idx, SoftAsciiChar::from_unchecked(ch))
+                (idx, SoftAsciiChar::rewrap(ch))
             })
     }
 
@@ -584,7 +1027,7 @@ impl<'a> Iterator for SoftAsciiCharIndices<'a> {
     fn last(self) -> Option<Self::Item> {
         self.inner.last()
             .map(|(idx, ch)| {
-                (idx, SoftAsciiChar::from_unchecked(ch))
+                (idx, SoftAsciiChar::rewrap(ch))
             })
     }
 }
@@ -593,7 +1036,7 @@ impl<'a> DoubleEndedIterator for SoftAsciiCharIndices<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.inner.next_back()
             .map(|(idx, ch)| {
-                (idx, SoftAsciiChar::from_unchecked(ch))
+                (idx, SoftAsciiChar::rewrap(ch))
             })
     }
 }
@@ -620,7 +1063,7 @@ impl<'a> Iterator for SoftAsciiLines<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next()
-            .map(SoftAsciiStr::from_unchecked)
+            .map(SoftAsciiStr::rewrap)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -632,7 +1075,7 @@ impl<'a> Iterator for SoftAsciiLines<'a> {
 impl<'a> DoubleEndedIterator for SoftAsciiLines<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.inner.next_back()
-            .map(SoftAsciiStr::from_unchecked)
+            .map(SoftAsciiStr::rewrap)
     }
 }
 
@@ -659,20 +1102,21 @@ impl<'a> Iterator for SoftAsciiSplitWhitespace<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next()
-            .map(SoftAsciiStr::from_unchecked)
+            .map(SoftAsciiStr::rewrap)
     }
 }
 
 impl<'a> DoubleEndedIterator for SoftAsciiSplitWhitespace<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.inner.next_back()
-            .map(SoftAsciiStr::from_unchecked)
+            .map(SoftAsciiStr::rewrap)
     }
 }
 
 
 #[cfg(test)]
 mod test {
+    #[cfg_attr(all(feature = "check-unchecked", not(feature = "alloc")), allow(dead_code))]
     const UTF8_STR: &str = "❤ == <3";
     //TODO write tests for simple wrapper
     // (use some fuzzing like test library and make sure operation on
@@ -680,8 +1124,10 @@ mod test {
 
     mod SoftAsciiStr {
         #![allow(non_snake_case)]
+        #[cfg_attr(all(feature = "check-unchecked", not(feature = "alloc")), allow(unused_imports))]
         use super::*;
         use super::super::SoftAsciiStr;
+        use soft_char::SoftAsciiChar;
         use std::ops::{Range, RangeInclusive, RangeFrom, RangeTo, RangeToInclusive, RangeFull};
 
         #[test]
@@ -690,9 +1136,28 @@ mod test {
                 SoftAsciiStr::from_str("hy ho\x00\x01\x02\x03").unwrap(),
                 "hy ho\x00\x01\x02\x03"
             );
-            assert!(SoftAsciiStr::from_str("↓").is_err());
+            let err = assert_err!(SoftAsciiStr::from_str("hy↓"));
+            assert_eq!(err.valid_up_to(), Some(2));
+            assert_eq!(err.invalid_char(), Some('↓'));
         }
 
+        #[test]
+        fn from_str_const() {
+            const CONTENT_TYPE: &SoftAsciiStr = SoftAsciiStr::from_str_const("Content-Type");
+            static UTF_8: &SoftAsciiStr = soft_ascii!(concat!("utf", "-8"));
+            assert_eq!(CONTENT_TYPE, "Content-Type");
+            assert_eq!(UTF_8, "utf-8");
+            assert_eq!(soft_ascii!(""), "");
+        }
+
+        #[test]
+        #[should_panic]
+        fn from_str_const_non_ascii_at_runtime() {
+            let s = String::from("a↓");
+            let _ = SoftAsciiStr::from_str_const(&s);
+        }
+
+        #[cfg(not(feature = "check-unchecked"))]
         #[test]
         fn from_unchecked() {
             assert_eq!(
@@ -701,9 +1166,23 @@ mod test {
             );
         }
 
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn from_str_lossy() {
+            use std::borrow::Cow;
+            let res = SoftAsciiStr::from_str_lossy("hy ho");
+            match res {
+                Cow::Borrowed(borrowed) => assert_eq!(borrowed, "hy ho"),
+                Cow::Owned(_) => panic!("ascii input should be borrowed")
+            }
+            let res = SoftAsciiStr::from_str_lossy(UTF8_STR);
+            assert_eq!(res.as_str(), "? == <3");
+        }
+
         #[test]
         fn revalidate_soft_constraint() {
-            let res = SoftAsciiStr::from_unchecked(UTF8_STR).revalidate_soft_constraint();
+            // `rewrap` isn't checked with `check-unchecked`
+            let res = SoftAsciiStr::rewrap(UTF8_STR).revalidate_soft_constraint();
             assert_eq!(UTF8_STR, assert_err!(res).into_source());
 
             let res = SoftAsciiStr::from_unchecked("hy").revalidate_soft_constraint();
@@ -715,6 +1194,96 @@ mod test {
 
         }
 
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn replace() {
+            let sas = SoftAsciiStr::from_str("a, b, c").unwrap();
+            let semicolon = SoftAsciiStr::from_str(";").unwrap();
+            let res = sas.replace(", ", semicolon);
+            assert_eq!(res, "a;b;c");
+            assert_eq!(sas.replacen(',', semicolon, 1), "a; b, c");
+            assert_eq!(sas.replacen(',', semicolon, 0), "a, b, c");
+            assert_eq!(sas.replace("", semicolon), ";a;,; ;b;,; ;c;");
+            assert_eq!(sas.replace('x', semicolon), "a, b, c");
+        }
+
+        #[test]
+        fn get() {
+            let sas = SoftAsciiStr::from_str("hy there").unwrap();
+            assert_eq!(sas.get(3..), Some(SoftAsciiStr::from_str("there").unwrap()));
+            assert_eq!(sas.get(..=1).unwrap(), "hy");
+            assert_eq!(sas.get(3..20), None);
+            let mut buf = String::from("hy there");
+            let sas = SoftAsciiStr::from_unchecked_mut(&mut buf);
+            sas.get_mut(..2).unwrap().inner_str_mut().make_ascii_uppercase();
+            assert!(sas.get_mut(9..).is_none());
+            assert_eq!(sas, "HY there");
+        }
+
+        #[test]
+        fn char_at() {
+            let sas = SoftAsciiStr::from_str("abc").unwrap();
+            assert_eq!(sas.char_at(1), Some(SoftAsciiChar::from_unchecked('b')));
+            assert_eq!(sas.char_at(3), None);
+        }
+
+        #[test]
+        fn char_at_broken_soft_constraint() {
+            let sas = SoftAsciiStr::rewrap("a↓b");
+            assert_eq!(sas.char_at(1), Some(SoftAsciiChar::rewrap('↓')));
+            assert_eq!(sas.char_at(2), None);
+            assert_eq!(sas.char_at(4), Some(SoftAsciiChar::from_unchecked('b')));
+        }
+
+        #[test]
+        fn inclusive_ranges() {
+            let mut buf = String::from("abcd");
+            let sas = SoftAsciiStr::from_unchecked_mut(&mut buf);
+            assert_eq!(&sas[1..=2], "bc");
+            assert_eq!(&sas[..=1], "ab");
+            sas[..=1].inner_str_mut().make_ascii_uppercase();
+            sas[2..=3].inner_str_mut().make_ascii_uppercase();
+            assert_eq!(sas, "ABCD");
+        }
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn to_ascii_case() {
+            let sas = SoftAsciiStr::from_unchecked("Content-Type: text/plain");
+            assert_eq!(sas.to_ascii_lowercase(), "content-type: text/plain");
+            assert_eq!(sas.to_ascii_uppercase(), "CONTENT-TYPE: TEXT/PLAIN");
+        }
+
+        #[test]
+        fn make_ascii_case() {
+            let mut buf = String::from("Hy There");
+            let sas_mut = SoftAsciiStr::from_unchecked_mut(&mut buf);
+            sas_mut.make_ascii_uppercase();
+            assert_eq!(sas_mut, "HY THERE");
+            sas_mut[3..].make_ascii_lowercase();
+            assert_eq!(sas_mut, "HY there");
+        }
+
+        #[test]
+        fn eq_ignore_ascii_case() {
+            let sas = SoftAsciiStr::from_unchecked("Content-Type");
+            assert!(sas.eq_ignore_ascii_case("content-type"));
+            assert!(sas.eq_ignore_ascii_case(SoftAsciiStr::from_unchecked("CONTENT-TYPE")));
+            assert!(sas.eq_ignore_ascii_case(&String::from("CoNtEnT-tYpE")));
+            assert!(!sas.eq_ignore_ascii_case("content_type"));
+            assert!(!sas.eq_ignore_ascii_case("content-typ"));
+        }
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn ascii_case_broken_soft_constraint() {
+            let sas = SoftAsciiStr::rewrap("Ä ist nicht Ascii");
+            assert_eq!(sas.to_ascii_lowercase(), "Ä ist nicht ascii");
+            assert_eq!(sas.to_ascii_uppercase(), "Ä IST NICHT ASCII");
+            assert!(sas.eq_ignore_ascii_case("Ä IST NICHT ASCII"));
+            assert!(!sas.eq_ignore_ascii_case("ä ist nicht ascii"));
+        }
+
         #[test]
         fn compile_bounds__get_unchecked() {
             let _ = SoftAsciiStr::get_unchecked::<Range<usize>>;
@@ -1,28 +1,45 @@
-use std::borrow::{Cow, Borrow};
-use std::ops::{Deref, DerefMut, AddAssign, Add};
-use std::cmp::PartialEq;
-use std::iter::{IntoIterator, FromIterator, Extend};
-use std::ops::{
+use core::ops::{Deref, DerefMut, AddAssign, Add};
+use core::cmp::PartialEq;
+use core::iter::{IntoIterator, FromIterator, Extend};
+use core::ops::{
     Index, IndexMut,
     Range, RangeFrom,
-    RangeTo, RangeFull,
+    RangeTo, RangeFull, RangeBounds,
+    RangeInclusive, RangeToInclusive
 };
+use core::fmt::{self, Display};
+use core::str::FromStr;
+#[cfg(feature = "check-unchecked")]
+use core::panic::Location;
+use alloc::borrow::{Cow, Borrow, ToOwned};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::string::{self, String};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use std::path::Path;
+#[cfg(feature = "std")]
 use std::ffi::OsStr;
+#[cfg(feature = "std")]
 use std::net::{ToSocketAddrs, SocketAddr};
-use std::fmt::{self, Display};
+#[cfg(feature = "std")]
 use std::{io, vec};
-use std::str::FromStr;
 
 // this import will become unused in future rust versions
 // but won't be removed for now for supporting current
 // rust versions
-#[allow(warnings)]
+#[cfg(feature = "std")]
+#[allow(unused_imports, deprecated)]
 use std::ascii::AsciiExt;
 
-use error::{StringFromStrError, FromSourceError};
+#[cfg(feature = "check-unchecked")]
+use check::{self, Origin};
+use error::{StringFromStrError, FromSourceError, Violation};
 use soft_char::SoftAsciiChar;
 use soft_str::SoftAsciiStr;
+use validate;
+use violation::{self, ValueType, Trigger};
 
 /// a `String` wrapper with an additional "is us-ascii" soft constraint
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -31,11 +48,23 @@ pub struct SoftAsciiString(String);
 impl SoftAsciiString {
 
     #[inline(always)]
+    #[cfg_attr(any(feature = "check-unchecked", feature = "track-unchecked", debug_assertions), track_caller)]
     pub fn from_unchecked<S: Into<String>>(s: S) -> Self {
-        SoftAsciiString(s.into())
+        let string = s.into();
+        #[cfg(feature = "check-unchecked")]
+        check::check(Origin::SoftAsciiString, &string);
+        violation::record_creation(&string);
+        SoftAsciiString(string)
     }
 
+    /// wraps a `String` derived from an existing soft value, it's never re-checked
     #[inline(always)]
+    pub(crate) fn rewrap(s: String) -> Self {
+        SoftAsciiString(s)
+    }
+
+    #[inline(always)]
+    #[cfg_attr(any(feature = "check-unchecked", feature = "track-unchecked", debug_assertions), track_caller)]
     #[deprecated(since="1.0.0", note="use from_unchecked")]
     pub fn from_string_unchecked<S: Into<String>>(s: S) -> Self {
         SoftAsciiString::from_unchecked(s)
@@ -44,10 +73,32 @@ impl SoftAsciiString {
     pub fn from_string<S>(source: S) -> Result<Self, FromSourceError<S>>
         where S: fmt::Debug + AsRef<str> + Into<String>
     {
-        if source.as_ref().is_ascii() {
-            Ok(Self::from_unchecked(source))
-        } else {
-            Err(FromSourceError::new(source))
+        match Violation::find(source.as_ref()) {
+            None => Ok(Self::rewrap(source.into())),
+            Some(violation) => Err(FromSourceError::from_violation(source, violation))
+        }
+    }
+
+    /// creates a `SoftAsciiString` replacing each non us-ascii char with `replacement`
+    ///
+    /// Note that the result is only ascii if `replacement` is ascii.
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn from_string_lossy(source: &str, replacement: SoftAsciiChar) -> Self {
+        if let Some(valid_up_to) = validate::first_non_ascii(source.as_bytes()) {
+            violation::report_in(ValueType::SoftAsciiString, Trigger::Lossy, source, valid_up_to);
+        }
+        let mut buf = SoftAsciiString::with_capacity(source.len());
+        buf.push_lossy(source, replacement);
+        buf
+    }
+
+    pub(crate) fn push_lossy(&mut self, source: &str, replacement: SoftAsciiChar) {
+        for ch in source.chars() {
+            if ch.is_ascii() {
+                self.0.push(ch)
+            } else {
+                self.push(replacement)
+            }
         }
     }
 
@@ -62,20 +113,82 @@ impl SoftAsciiString {
     }
 
 
+    /// returns `self` if it's us-ascii, else the inner `String`
+    ///
+    /// As the error is the plain `String` it has no `created_at` location
+    /// like `FromSourceError`. With the `track-unchecked` feature it can be
+    /// looked up through `track::created_at` before calling this:
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "track-unchecked", not(feature = "check-unchecked")))] {
+    /// use soft_ascii_string::{track, SoftAsciiString};
+    ///
+    /// let string = SoftAsciiString::from_unchecked("a↓");
+    /// let created_at = track::created_at(&string);
+    /// if let Err(source) = string.revalidate_soft_constraint() {
+    ///     println!("{:?} created at {}", source, created_at.unwrap());
+    /// }
+    /// # }
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
     pub fn revalidate_soft_constraint(self) -> Result<SoftAsciiString, String> {
-        if self.is_ascii() {
-            Ok(self)
-        } else {
-            Err(self.0)
+        match validate::first_non_ascii(self.as_bytes()) {
+            None => Ok(self),
+            Some(valid_up_to) => {
+                violation::report_in(ValueType::SoftAsciiString, Trigger::Revalidate,
+                    &self.0, valid_up_to);
+                Err(self.0)
+            }
         }
     }
 
-    //TODO warn in doc
+    /// repairs a broken soft constraint
+    ///
+    /// Replaces each non us-ascii char with `SoftAsciiChar::REPLACEMENT_CHARACTER`,
+    /// this is meant to be used if the soft constraint might have been broken e.g.
+    /// through `inner_string_mut`. The us-ascii prefix is kept in place.
+    ///
+    /// A violation is reported to the `violation` module.
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn sanitize(&mut self) {
+        if let Some(valid_up_to) = validate::first_non_ascii(self.0.as_bytes()) {
+            violation::report_in(ValueType::SoftAsciiString, Trigger::Sanitize,
+                &self.0, valid_up_to);
+            let tail = self.0.split_off(valid_up_to);
+            self.push_lossy(&tail, SoftAsciiChar::REPLACEMENT_CHARACTER);
+        }
+    }
+
+    /// returns a mutable `String` reference to the inner buffer
+    ///
+    /// # Soft Constraint
+    /// be aware that it is very easy to introduce bugs when
+    /// directly editing a `SoftAsciiString` as an `String`, see
+    /// `SoftAsciiStr::inner_str_mut`.
+    ///
+    /// Changes made through the returned reference are _not_ re-checked,
+    /// not even with the `check-unchecked` feature, use
+    /// `inner_string_mut_checked` for that.
     #[inline]
     pub fn inner_string_mut(&mut self) -> &mut String {
         &mut self.0
     }
 
+    /// like `inner_string_mut` but the soft constraint is re-checked when the returned guard is dropped
+    ///
+    /// # Panics
+    ///
+    /// like `SoftAsciiStr::inner_str_mut_checked`
+    #[cfg(feature = "check-unchecked")]
+    #[inline]
+    #[track_caller]
+    pub fn inner_string_mut_checked(&mut self) -> InnerStringMut<'_> {
+        InnerStringMut {
+            inner: &mut self.0,
+            location: Location::caller()
+        }
+    }
+
     #[inline]
     pub fn inner_string(&self) -> &String {
         &self.0
@@ -98,11 +211,11 @@ impl SoftAsciiString {
 
     pub fn pop(&mut self) -> Option<SoftAsciiChar> {
         self.0.pop()
-            .map(SoftAsciiChar::from_unchecked)
+            .map(SoftAsciiChar::rewrap)
     }
 
     pub fn remove(&mut self, idx: usize) -> SoftAsciiChar {
-        SoftAsciiChar::from_unchecked(self.0.remove(idx))
+        SoftAsciiChar::rewrap(self.0.remove(idx))
     }
 
     #[inline]
@@ -117,17 +230,17 @@ impl SoftAsciiString {
 
     #[inline]
     pub fn as_soft_ascii_str(&self) -> &SoftAsciiStr {
-        SoftAsciiStr::from_unchecked(self.0.as_str())
+        SoftAsciiStr::rewrap(self.0.as_str())
     }
 
     #[inline]
     pub fn as_soft_ascii_str_mut(&mut self) -> &mut SoftAsciiStr {
-        SoftAsciiStr::from_unchecked_mut(self.0.as_mut_str())
+        SoftAsciiStr::rewrap_mut(self.0.as_mut_str())
     }
 
     #[inline]
     pub fn split_off(&mut self, at: usize) -> SoftAsciiString {
-        SoftAsciiString::from_unchecked(self.0.split_off(at))
+        SoftAsciiString::rewrap(self.0.split_off(at))
     }
 
     #[inline]
@@ -145,6 +258,35 @@ impl SoftAsciiString {
         self.0.is_ascii()
     }
 
+    /// replaces the given byte range with `replace_with`
+    ///
+    /// # Panics
+    /// if the start or end of the range is not on a char boundary or out of bounds
+    #[inline]
+    pub fn replace_range<R>(&mut self, range: R, replace_with: &SoftAsciiStr)
+        where R: RangeBounds<usize>
+    {
+        self.0.replace_range(range, replace_with.as_str())
+    }
+
+    /// retains only the chars for which `keep` returns true
+    pub fn retain<F>(&mut self, mut keep: F)
+        where F: FnMut(SoftAsciiChar) -> bool
+    {
+        self.0.retain(|ch| keep(SoftAsciiChar::rewrap(ch)))
+    }
+
+    /// removes the given byte range returning an iterator over the removed chars
+    ///
+    /// # Panics
+    /// if the start or end of the range is not on a char boundary or out of bounds
+    #[inline]
+    pub fn drain<R>(&mut self, range: R) -> SoftAsciiDrain<'_>
+        where R: RangeBounds<usize>
+    {
+        SoftAsciiDrain { inner: self.0.drain(range) }
+    }
+
 }
 
 macro_rules! impl_wrapping {
@@ -187,6 +329,78 @@ impl_wrapping_mut! {
     fn clear(&mut self) -> ()
 }
 
+/// a wrapper around `Drain` turning each char into a `SoftAsciiChar`
+///
+/// This iterator is returned by `SoftAsciiString::drain` instead of `Drain`.
+#[derive(Debug)]
+pub struct SoftAsciiDrain<'a> {
+    inner: string::Drain<'a>
+}
+
+impl<'a> SoftAsciiDrain<'a> {
+
+    /// returns the remaining (not yet yielded) chars
+    #[inline]
+    pub fn as_soft_ascii_str(&self) -> &SoftAsciiStr {
+        SoftAsciiStr::rewrap(self.inner.as_str())
+    }
+}
+
+impl<'a> Iterator for SoftAsciiDrain<'a> {
+    type Item = SoftAsciiChar;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+            .map(SoftAsciiChar::rewrap)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for SoftAsciiDrain<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+            .map(SoftAsciiChar::rewrap)
+    }
+}
+
+/// mutable access to the `String` of a `SoftAsciiString`, returned by `SoftAsciiString::inner_string_mut_checked`
+#[cfg(feature = "check-unchecked")]
+#[derive(Debug)]
+pub struct InnerStringMut<'a> {
+    inner: &'a mut String,
+    location: &'static Location<'static>
+}
+
+#[cfg(feature = "check-unchecked")]
+impl<'a> Deref for InnerStringMut<'a> {
+    type Target = String;
+
+    #[inline]
+    fn deref(&self) -> &String {
+        self.inner
+    }
+}
+
+#[cfg(feature = "check-unchecked")]
+impl<'a> DerefMut for InnerStringMut<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut String {
+        self.inner
+    }
+}
+
+#[cfg(feature = "check-unchecked")]
+impl<'a> Drop for InnerStringMut<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        check::check_on_drop(Origin::InnerStringMut, self.inner, self.location);
+    }
+}
+
 impl Borrow<str> for SoftAsciiString {
     #[inline]
     fn borrow(&self) -> &str {
@@ -196,7 +410,7 @@ impl Borrow<str> for SoftAsciiString {
 
 impl Borrow<SoftAsciiStr> for SoftAsciiString {
     fn borrow(&self) -> &SoftAsciiStr {
-        &*self
+        self
     }
 }
 
@@ -243,7 +457,7 @@ impl<'a> PartialEq<&'a str> for SoftAsciiString {
     }
 }
 
-impl<'a> PartialEq<SoftAsciiString> for &'a str {
+impl PartialEq<SoftAsciiString> for &str {
     fn eq(&self, other: &SoftAsciiString) -> bool {
         other.as_str() == *self
     }
@@ -277,17 +491,17 @@ impl<'a> PartialEq<&'a SoftAsciiStr> for SoftAsciiString {
     }
 }
 
-impl<'a> PartialEq<SoftAsciiString> for Cow<'a, str> {
+impl PartialEq<SoftAsciiString> for Cow<'_, str> {
     #[inline]
     fn eq(&self, other: &SoftAsciiString) -> bool {
-        other.as_str() == &*self
+        *other.as_str() == **self
     }
 }
 
-impl<'a> PartialEq<Cow<'a, str>> for SoftAsciiString {
+impl PartialEq<Cow<'_, str>> for SoftAsciiString {
     #[inline]
-    fn eq(&self, other: &Cow<'a, str>) -> bool {
-        self.as_str() == &*other
+    fn eq(&self, other: &Cow<'_, str>) -> bool {
+        *self.as_str() == **other
     }
 }
 
@@ -359,7 +573,7 @@ impl<'a> FromIterator<&'a SoftAsciiStr> for SoftAsciiString {
 impl AsRef<SoftAsciiStr> for SoftAsciiString {
     #[inline]
     fn as_ref(&self) -> &SoftAsciiStr {
-        &*self
+        self
     }
 }
 
@@ -377,6 +591,7 @@ impl AsRef<[u8]> for SoftAsciiString {
     }
 }
 
+#[cfg(feature = "std")]
 impl AsRef<OsStr> for SoftAsciiString {
     #[inline]
     fn as_ref(&self) -> &OsStr {
@@ -384,6 +599,7 @@ impl AsRef<OsStr> for SoftAsciiString {
     }
 }
 
+#[cfg(feature = "std")]
 impl AsRef<Path> for SoftAsciiString {
     #[inline]
     fn as_ref(&self) -> &Path {
@@ -391,6 +607,7 @@ impl AsRef<Path> for SoftAsciiString {
     }
 }
 
+#[cfg(feature = "std")]
 impl ToSocketAddrs for SoftAsciiString {
     type Iter = vec::IntoIter<SocketAddr>;
 
@@ -404,7 +621,7 @@ macro_rules! impl_index {
         impl Index<$idx> for SoftAsciiString {
             type Output = SoftAsciiStr;
             fn index(&self, index: $idx) -> &Self::Output {
-                SoftAsciiStr::from_unchecked(self.0.index(index))
+                SoftAsciiStr::rewrap(self.0.index(index))
             }
         }
     )*);
@@ -414,14 +631,16 @@ impl_index! {
     Range<usize>,
     RangeFrom<usize>,
     RangeTo<usize>,
-    RangeFull
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>
 }
 
 macro_rules! impl_index_mut {
     ($($idx:ty),*) => ($(
         impl IndexMut<$idx> for SoftAsciiString {
             fn index_mut(&mut self, index: $idx) -> &mut Self::Output {
-                SoftAsciiStr::from_unchecked_mut(self.0.index_mut(index))
+                SoftAsciiStr::rewrap_mut(self.0.index_mut(index))
             }
         }
     )*);
@@ -431,7 +650,9 @@ impl_index_mut! {
     Range<usize>,
     RangeFrom<usize>,
     RangeTo<usize>,
-    RangeFull
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>
 }
 
 impl Extend<SoftAsciiChar> for SoftAsciiString {
@@ -460,7 +681,7 @@ impl Extend<SoftAsciiString> for SoftAsciiString {
         where I: IntoIterator<Item=SoftAsciiString>
     {
         for string in iter {
-            self.push_str(&*string);
+            self.push_str(&string);
         }
     }
 }
@@ -511,6 +732,38 @@ impl From<Box<SoftAsciiStr>> for SoftAsciiString {
     }
 }
 
+impl From<SoftAsciiString> for Arc<SoftAsciiStr> {
+    fn from(s: SoftAsciiString) -> Arc<SoftAsciiStr> {
+        let arc: Arc<str> = Arc::from(s.0);
+        // Safe: SoftAsciiStr is a `repr(transparent)` wrapper of str
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const SoftAsciiStr) }
+    }
+}
+
+impl<'a> From<&'a SoftAsciiStr> for Arc<SoftAsciiStr> {
+    fn from(s: &'a SoftAsciiStr) -> Arc<SoftAsciiStr> {
+        let arc: Arc<str> = Arc::from(s.as_str());
+        // Safe: SoftAsciiStr is a `repr(transparent)` wrapper of str
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const SoftAsciiStr) }
+    }
+}
+
+impl From<SoftAsciiString> for Rc<SoftAsciiStr> {
+    fn from(s: SoftAsciiString) -> Rc<SoftAsciiStr> {
+        let rc: Rc<str> = Rc::from(s.0);
+        // Safe: SoftAsciiStr is a `repr(transparent)` wrapper of str
+        unsafe { Rc::from_raw(Rc::into_raw(rc) as *const SoftAsciiStr) }
+    }
+}
+
+impl<'a> From<&'a SoftAsciiStr> for Rc<SoftAsciiStr> {
+    fn from(s: &'a SoftAsciiStr) -> Rc<SoftAsciiStr> {
+        let rc: Rc<str> = Rc::from(s.as_str());
+        // Safe: SoftAsciiStr is a `repr(transparent)` wrapper of str
+        unsafe { Rc::from_raw(Rc::into_raw(rc) as *const SoftAsciiStr) }
+    }
+}
+
 impl Display for SoftAsciiString {
     #[inline]
     fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
@@ -518,18 +771,18 @@ impl Display for SoftAsciiString {
     }
 }
 
-impl Into<Vec<u8>> for SoftAsciiString {
+impl From<SoftAsciiString> for Vec<u8> {
 
     #[inline]
-    fn into(self) -> Vec<u8> {
-        self.0.into()
+    fn from(s: SoftAsciiString) -> Vec<u8> {
+        s.0.into()
     }
 }
 
-impl Into<String> for SoftAsciiString {
+impl From<SoftAsciiString> for String {
     #[inline]
-    fn into(self) -> String {
-        self.0
+    fn from(s: SoftAsciiString) -> String {
+        s.0
     }
 }
 
@@ -537,10 +790,9 @@ impl FromStr for SoftAsciiString {
     type Err = StringFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.is_ascii() {
-            Ok(SoftAsciiString(s.to_owned()))
-        } else {
-            Err(StringFromStrError)
+        match Violation::find(s) {
+            None => Ok(SoftAsciiString(s.to_owned())),
+            Some(violation) => Err(StringFromStrError::from_violation(violation))
         }
     }
 }
@@ -549,7 +801,7 @@ impl FromStr for SoftAsciiString {
 mod tests {
     use std::str;
     use std::borrow::Borrow;
-    use ::SoftAsciiStr;
+    use ::{SoftAsciiStr, SoftAsciiChar};
 
     const SOME_NOT_ASCII: &str = "malformed←";
     const SOME_ASCII: &str = "hy there";
@@ -563,6 +815,7 @@ mod tests {
         use super::*;
         use super::super::SoftAsciiString;
 
+        #[cfg(not(feature = "check-unchecked"))]
         #[test]
         fn from_unchecked() {
             let sas = SoftAsciiString::from_unchecked(SOME_ASCII);
@@ -623,11 +876,35 @@ mod tests {
                 SoftAsciiString::from_unchecked(SOME_ASCII);
             assert_ok!(sas.revalidate_soft_constraint());
 
+            // `rewrap` isn't checked with `check-unchecked`
             let bad: SoftAsciiString =
-                SoftAsciiString::from_unchecked(SOME_NOT_ASCII);
+                SoftAsciiString::rewrap(SOME_NOT_ASCII.to_owned());
             assert_err!(bad.revalidate_soft_constraint());
         }
 
+        #[test]
+        fn from_string_lossy() {
+            let sas = SoftAsciiString::from_string_lossy(
+                SOME_NOT_ASCII, SoftAsciiChar::from_unchecked('_'));
+            assert_eq!(sas, "malformed_");
+            let sas = SoftAsciiString::from_string_lossy(
+                SOME_ASCII, SoftAsciiChar::from_unchecked('_'));
+            assert_eq!(sas, SOME_ASCII);
+        }
+
+        #[test]
+        fn sanitize() {
+            let mut sas = SoftAsciiString::rewrap("a↓b←←".to_owned());
+            sas.sanitize();
+            assert_eq!(sas, "a?b??");
+            assert!(sas.is_ascii());
+
+            let mut sas = SoftAsciiString::from_unchecked(SOME_ASCII);
+            sas.inner_string_mut().push('→');
+            sas.sanitize();
+            assert_eq!(sas, "hy there?");
+        }
+
         #[test]
         fn has_into_vec_u8() {
             let sas = SoftAsciiString::from_unchecked("test");
@@ -653,12 +930,63 @@ mod tests {
             assert_eq!(string, string);
         }
 
+        #[test]
+        fn inclusive_ranges() {
+            let mut sas = SoftAsciiString::from_unchecked("abcd");
+            assert_eq!(&sas[1..=2], "bc");
+            assert_eq!(&sas[..=1], "ab");
+            sas[..=1].inner_str_mut().make_ascii_uppercase();
+            assert_eq!(sas, "ABcd");
+        }
+
+        #[test]
+        fn replace_range() {
+            let mut sas = SoftAsciiString::from_unchecked("hy there");
+            sas.replace_range(..2, SoftAsciiStr::from_unchecked("hello"));
+            assert_eq!(sas, "hello there");
+            sas.replace_range(5.., SoftAsciiStr::from_unchecked(""));
+            assert_eq!(sas, "hello");
+        }
+
+        #[test]
+        fn retain() {
+            let mut sas = SoftAsciiString::from_unchecked("a1b2c3");
+            sas.retain(|ch| ch.is_alphabetic());
+            assert_eq!(sas, "abc");
+        }
+
+        #[test]
+        fn drain() {
+            let mut sas = SoftAsciiString::from_unchecked("hy there");
+            {
+                let mut drain = sas.drain(2..);
+                assert_eq!(drain.next(), Some(SoftAsciiChar::from_unchecked(' ')));
+                assert_eq!(drain.next_back(), Some(SoftAsciiChar::from_unchecked('e')));
+                assert_eq!(drain.as_soft_ascii_str(), "ther");
+            }
+            assert_eq!(sas, "hy");
+            let drained: Vec<SoftAsciiChar> = sas.drain(..).collect();
+            assert_eq!(drained, vec!['h', 'y']);
+            assert!(sas.is_empty());
+        }
+
+        #[test]
+        fn ascii_case() {
+            let mut sas = SoftAsciiString::from_unchecked("Hy There");
+            assert_eq!(sas.to_ascii_uppercase(), "HY THERE");
+            sas.make_ascii_lowercase();
+            assert_eq!(sas, "hy there");
+            assert!(sas.eq_ignore_ascii_case("HY THERE"));
+        }
+
         #[test]
         fn from_str() {
             use std::str::FromStr;
             let s: SoftAsciiString = assert_ok!(FromStr::from_str("hy ho"));
             assert_eq!(s, "hy ho");
-            assert_err!("↓".parse::<SoftAsciiString>());
+            let err = assert_err!("ab↓".parse::<SoftAsciiString>());
+            assert_eq!(err.valid_up_to(), Some(2));
+            assert_eq!(err.invalid_char(), Some('↓'));
         }
     }
 }
@@ -0,0 +1,257 @@
+//! Tracking where unchecked values were created
+//!
+//! With the `track-unchecked` feature `SoftAsciiStr::from_unchecked`,
+//! `SoftAsciiStr::from_unchecked_mut` and `SoftAsciiString::from_unchecked`
+//! record the location of their caller in a process wide side table keyed
+//! by the address of the wrapped bytes. If `revalidate_soft_constraint`
+//! fails the location is available through `FromSourceError::created_at`
+//! (and `ViolationInfo::created_at` for the `violation` hook).
+//! Without the feature the same (crate-internal) table is used in debug
+//! builds with `std` for `ViolationInfo::created_at` only.
+//!
+//! This is a debugging aid with some limitations:
+//!
+//! - every unchecked construction locks a mutex and hashes the value, so it's slow
+//! - `SoftAsciiChar` is a `Copy` value without an address, it's not tracked
+//! - a `SoftAsciiString` is only found as long as its buffer is not reallocated
+//! - only values which contain a whole unchecked value are found, e.g. a
+//!   sub-slice of an unchecked value is not
+//! - at most `MAX_ENTRIES` values are tracked, older ones are forgotten
+//!
+//! Entries are not removed when a value is dropped, but each entry stores a
+//! hash of the value, so if the memory was reused by another value the
+//! entry is ignored. `clear` removes all entries.
+//!
+//! # Example
+//!
+//! ```
+//! # // the module is crate-internal without the feature
+//! # #[cfg(feature = "track-unchecked")] {
+//! use soft_ascii_string::{track, SoftAsciiString};
+//!
+//! let string = SoftAsciiString::from_unchecked("abc");
+//! if let Some(location) = track::created_at(&string) {
+//!     println!("created at {}", location);
+//! }
+//! # }
+//! ```
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::panic::Location;
+use std::collections::{BTreeMap, VecDeque};
+use std::collections::hash_map::DefaultHasher;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::vec::Vec;
+
+use soft_str::SoftAsciiStr;
+
+/// the maximal number of tracked values
+pub const MAX_ENTRIES: usize = 1 << 16;
+
+struct Entry {
+    end: usize,
+    hash: u64,
+    // to tell if `Table::order` still refers to this entry
+    seq: u64,
+    location: &'static Location<'static>
+}
+
+struct Table {
+    // maps the start address of a tracked value to the entry for it
+    entries: BTreeMap<usize, Entry>,
+    // start address and seq of recorded values, oldest first
+    order: VecDeque<(usize, u64)>,
+    next_seq: u64,
+    max_entries: usize
+}
+
+impl Table {
+
+    const fn new(max_entries: usize) -> Self {
+        Table {
+            entries: BTreeMap::new(),
+            order: VecDeque::new(),
+            next_seq: 0,
+            max_entries
+        }
+    }
+
+    fn record(&mut self, value: &str, location: &'static Location<'static>) {
+        let start = value.as_ptr() as usize;
+        let end = start + value.len();
+        // entries starting within the value are stale, their memory was reused
+        let stale: Vec<usize> = self.entries.range(start..end).map(|(&start, _)| start).collect();
+        for start in stale {
+            self.entries.remove(&start);
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.entries.insert(start, Entry { end, hash: hash(value), seq, location });
+        self.order.push_back((start, seq));
+        // `order` has an element for each entry (and some for removed ones)
+        while self.order.len() > self.max_entries {
+            if let Some((start, seq)) = self.order.pop_front() {
+                if self.entries.get(&start).map(|entry| entry.seq) == Some(seq) {
+                    self.entries.remove(&start);
+                }
+            }
+        }
+    }
+
+    fn lookup(&self, source: &str, offset: usize) -> Option<&'static Location<'static>> {
+        let source_start = source.as_ptr() as usize;
+        let addr = source_start + offset;
+        self.entries.range(source_start..=addr).rev()
+            .find(|&(&start, entry)| {
+                let range = start - source_start..entry.end - source_start;
+                addr < entry.end
+                    && range.end <= source.len()
+                    && hash(&source[range]) == entry.hash
+            })
+            .map(|(_, entry)| entry.location)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+fn hash(value: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(value.as_bytes());
+    hasher.finish()
+}
+
+static TABLE: Mutex<Table> = Mutex::new(Table::new(MAX_ENTRIES));
+
+fn table() -> MutexGuard<'static, Table> {
+    // the table is consistent after each operation, so poisoning can be ignored
+    TABLE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// returns where the (unchecked) value starting at the first byte of `value` was created
+pub fn created_at(value: &SoftAsciiStr) -> Option<&'static Location<'static>> {
+    if value.is_empty() {
+        None
+    } else {
+        lookup(value.as_str(), 0)
+    }
+}
+
+/// removes all entries from the side table
+pub fn clear() {
+    table().clear()
+}
+
+/// records that `value` was created by a unchecked constructor at `location`
+pub(crate) fn record(value: &str, location: &'static Location<'static>) {
+    if !value.is_empty() {
+        table().record(value, location)
+    }
+}
+
+/// returns where the value in `source` containing the byte at `offset` was created
+pub(crate) fn lookup(source: &str, offset: usize) -> Option<&'static Location<'static>> {
+    table().lookup(source, offset)
+}
+
+/// the creation site stored in `FromSourceError`
+///
+/// It's ignored by `PartialEq` and `Hash`, as it's only meant for debugging.
+#[derive(Copy, Clone, Default)]
+pub(crate) struct CreatedAt(pub(crate) Option<&'static Location<'static>>);
+
+impl fmt::Debug for CreatedAt {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, fter)
+    }
+}
+
+impl PartialEq for CreatedAt {
+    fn eq(&self, _other: &CreatedAt) -> bool {
+        true
+    }
+}
+
+impl Eq for CreatedAt {}
+
+impl Hash for CreatedAt {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+#[cfg(all(test, not(feature = "check-unchecked")))]
+mod test {
+    use core::panic::Location;
+    use ::{SoftAsciiStr, SoftAsciiString};
+    use super::{created_at, Table};
+
+    #[cfg(feature = "track-unchecked")]
+    #[test]
+    fn revalidation_error_has_creation_site() {
+        let buf = String::from("ab↓c");
+        let soft = SoftAsciiStr::from_unchecked(&buf);
+        let line = line!() - 1;
+        let err = assert_err!(soft.revalidate_soft_constraint());
+        let location = err.created_at().unwrap();
+        assert_eq!(location.line(), line);
+        assert!(location.file().ends_with("track.rs"));
+
+        // sub slices are not found
+        let err = assert_err!(soft[1..].revalidate_soft_constraint());
+        assert!(err.created_at().is_none());
+    }
+
+    #[test]
+    fn soft_ascii_string() {
+        let string = SoftAsciiString::from_unchecked("a↓");
+        let line = line!() - 1;
+        assert_eq!(created_at(&string).unwrap().line(), line);
+        // the error of an owned value is the plain `String`
+        assert_eq!(assert_err!(string.revalidate_soft_constraint()), "a↓");
+
+        assert!(created_at(SoftAsciiStr::from_unchecked("")).is_none());
+    }
+
+    #[cfg(feature = "track-unchecked")]
+    #[test]
+    fn newer_value_in_reused_memory_wins() {
+        let mut buf = String::from("abcdef");
+        let _ = SoftAsciiStr::from_unchecked(&buf[2..4]);
+        buf.push('↓');
+        buf.shrink_to_fit();
+        let outer = SoftAsciiStr::from_unchecked(&buf);
+        let line = line!() - 1;
+        let err = assert_err!(outer.revalidate_soft_constraint());
+        assert_eq!(err.created_at().unwrap().line(), line);
+        assert_eq!(created_at(outer).unwrap().line(), line);
+    }
+
+    #[test]
+    fn changed_memory_is_not_found() {
+        let mut table = Table::new(8);
+        let mut buf = String::from("abc↓");
+        table.record(&buf, Location::caller());
+        assert!(table.lookup(&buf, 3).is_some());
+        buf.make_ascii_uppercase();
+        assert!(table.lookup(&buf, 3).is_none());
+    }
+
+    #[test]
+    fn oldest_entries_are_evicted() {
+        let mut table = Table::new(2);
+        let buf = String::from("abc");
+        // recording the same value again doesn't evict other entries
+        for _ in 0..3 {
+            table.record(&buf[0..1], Location::caller());
+        }
+        table.record(&buf[1..2], Location::caller());
+        assert!(table.lookup(&buf[0..1], 0).is_some());
+        table.record(&buf[2..3], Location::caller());
+        assert!(table.lookup(&buf[0..1], 0).is_none());
+        assert!(table.lookup(&buf[1..2], 0).is_some());
+        assert!(table.lookup(&buf[2..3], 0).is_some());
+        assert_eq!(table.entries.len(), 2);
+        assert_eq!(table.order.len(), 2);
+    }
+}
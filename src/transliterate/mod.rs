@@ -0,0 +1,198 @@
+//! Unicode to us-ascii transliteration
+//!
+//! Converts arbitrary text into a human readable us-ascii approximation,
+//! e.g. `"Ärger – “naïve” Straße"` becomes `"Aerger - \"naive\" Strasse"`.
+//! As every char is either mapped using an embedded table or handled by
+//! the chosen [`Fallback`](enum.Fallback.html) the result is ascii by
+//! construction (as long as the replacement char of `Fallback::Replace`
+//! is ascii).
+//!
+//! The table covers the latin scripts (including vietnamese), basic
+//! greek and cyrillic, common punctuation, currency and math symbols,
+//! ligatures and fullwidth forms. Combining marks are dropped, so
+//! decomposed input like `"e\u{301}"` is handled, too.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::transliterate::{transliterate, Fallback};
+//!
+//! let ascii = transliterate("Grüße – 5 €", Fallback::default());
+//! assert_eq!(ascii, "Gruesse - 5 EUR");
+//!
+//! let ascii = transliterate("snow ☃", Fallback::Escape);
+//! assert_eq!(ascii, "snow \\u{2603}");
+//! ```
+use soft_char::SoftAsciiChar;
+use soft_str::SoftAsciiStr;
+use soft_string::SoftAsciiString;
+
+use self::table::TABLE;
+
+mod table;
+
+/// what to do with chars which have no mapping in the transliteration table
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Fallback {
+    /// remove the char
+    Drop,
+    /// replace the char with the given char
+    Replace(SoftAsciiChar),
+    /// replace the char with a rust style unicode escape like `\u{2603}`
+    Escape
+}
+
+impl Default for Fallback {
+    /// `Fallback::Replace(SoftAsciiChar::REPLACEMENT_CHARACTER)`
+    fn default() -> Self {
+        Fallback::Replace(SoftAsciiChar::REPLACEMENT_CHARACTER)
+    }
+}
+
+/// transliterates `input` into a new `SoftAsciiString`
+pub fn transliterate(input: &str, fallback: Fallback) -> SoftAsciiString {
+    let mut out = SoftAsciiString::with_capacity(input.len());
+    transliterate_into(input, fallback, &mut out);
+    out
+}
+
+/// transliterates `input` appending the result to `out`
+pub fn transliterate_into(input: &str, fallback: Fallback, out: &mut SoftAsciiString) {
+    for ch in input.chars() {
+        if ch.is_ascii() {
+            out.push(SoftAsciiChar::rewrap(ch));
+            continue;
+        }
+        match transliterate_char(ch) {
+            Some(mapped) => out.push_str(mapped),
+            None => match fallback {
+                Fallback::Drop => {},
+                Fallback::Replace(replacement) => out.push(replacement),
+                Fallback::Escape => {
+                    for escaped in ch.escape_unicode() {
+                        out.push(SoftAsciiChar::rewrap(escaped));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// returns the transliteration of a single char
+///
+/// Ascii chars are mapped to themself, combining marks are mapped
+/// to an empty string. `None` is returned if there is no mapping.
+pub fn transliterate_char(ch: char) -> Option<&'static SoftAsciiStr> {
+    if ch.is_ascii() {
+        let idx = ch as usize;
+        return Some(SoftAsciiStr::rewrap(&ASCII_CHARS[idx..idx + 1]));
+    }
+    if is_combining_mark(ch) {
+        return Some(SoftAsciiStr::rewrap(""));
+    }
+    TABLE.binary_search_by_key(&ch, |&(key, _)| key)
+        .ok()
+        .map(|idx| SoftAsciiStr::rewrap(TABLE[idx].1))
+}
+
+/// all 128 ascii chars ordered by code point
+const ASCII_CHARS: &str = "\
+    \x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\
+    \x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1a\x1b\x1c\x1d\x1e\x1f\
+    \x20!\"#$%&'()*+,-./0123456789:;<=>?\
+    @ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_\
+    `abcdefghijklmnopqrstuvwxyz{|}~\x7f";
+
+fn is_combining_mark(ch: char) -> bool {
+    matches!(ch,
+        '\u{300}'..='\u{36f}'
+        | '\u{1ab0}'..='\u{1aff}'
+        | '\u{1dc0}'..='\u{1dff}'
+        | '\u{20d0}'..='\u{20ff}'
+        | '\u{fe20}'..='\u{fe2f}')
+}
+
+#[cfg(test)]
+mod test {
+    use soft_char::SoftAsciiChar;
+    use super::{transliterate, transliterate_char, Fallback, ASCII_CHARS};
+    use super::table::TABLE;
+
+    #[test]
+    fn table_is_sorted() {
+        for window in TABLE.windows(2) {
+            assert!(window[0].0 < window[1].0, "not sorted at {:?}", window[1].0);
+        }
+    }
+
+    #[test]
+    fn table_is_ascii() {
+        for &(ch, mapped) in TABLE {
+            assert!(!ch.is_ascii(), "ascii char in table: {:?}", ch);
+            assert!(mapped.is_ascii(), "non ascii mapping for {:?}", ch);
+        }
+    }
+
+    #[test]
+    fn ascii_chars_are_complete() {
+        assert_eq!(ASCII_CHARS.len(), 128);
+        for (idx, ch) in ASCII_CHARS.char_indices() {
+            assert_eq!(ch as usize, idx);
+        }
+    }
+
+    #[test]
+    fn ascii_is_kept() {
+        let input = "hy there\r\n\t~!";
+        assert_eq!(transliterate(input, Fallback::Drop), input);
+    }
+
+    #[test]
+    fn german() {
+        let res = transliterate("Ärger über Öl, Größe", Fallback::default());
+        assert_eq!(res, "Aerger ueber Oel, Groesse");
+    }
+
+    #[test]
+    fn accents_and_punctuation() {
+        let res = transliterate("“café” – naïve…", Fallback::default());
+        assert_eq!(res, "\"cafe\" - naive...");
+    }
+
+    #[test]
+    fn decomposed_input() {
+        let res = transliterate("e\u{301}le\u{300}ve", Fallback::default());
+        assert_eq!(res, "eleve");
+    }
+
+    #[test]
+    fn cyrillic() {
+        let res = transliterate("Щука", Fallback::default());
+        assert_eq!(res, "Shchuka");
+    }
+
+    #[test]
+    fn fallback_drop() {
+        assert_eq!(transliterate("a☃b", Fallback::Drop), "ab");
+    }
+
+    #[test]
+    fn fallback_replace() {
+        let fallback = Fallback::Replace(SoftAsciiChar::from_unchecked('_'));
+        assert_eq!(transliterate("a☃b", fallback), "a_b");
+    }
+
+    #[test]
+    fn fallback_escape() {
+        assert_eq!(transliterate("a☃b😀", Fallback::Escape), "a\\u{2603}b\\u{1f600}");
+    }
+
+    #[test]
+    fn single_chars() {
+        assert_eq!(transliterate_char('ß').unwrap(), "ss");
+        assert_eq!(transliterate_char('a').unwrap(), "a");
+        assert_eq!(transliterate_char('\u{7f}').unwrap(), "\u{7f}");
+        assert_eq!(transliterate_char('\u{301}').unwrap(), "");
+        assert!(transliterate_char('☃').is_none());
+    }
+}
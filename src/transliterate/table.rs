@@ -0,0 +1,277 @@
+//! the mapping table used by `transliterate`
+//!
+//! Entries have to be sorted by code point and all replacements have
+//! to be us-ascii (both is checked by tests). Most entries are derived
+//! from the compatibility decomposition of the char with all combining
+//! marks removed, some are adjusted to the common (e.g. german)
+//! transliteration.
+
+pub(super) static TABLE: &[(char, &str)] = &[
+    ('\u{a0}', " "), ('\u{a1}', "!"), ('\u{a2}', "c"), ('\u{a3}', "GBP"), ('\u{a5}', "JPY"),
+    ('\u{a6}', "|"), ('\u{a7}', "S"), ('\u{a8}', " "), ('\u{a9}', "(C)"), ('\u{aa}', "a"),
+    ('\u{ab}', "<<"), ('\u{ad}', ""), ('\u{ae}', "(R)"), ('\u{af}', "-"), ('\u{b1}', "+/-"),
+    ('\u{b2}', "2"), ('\u{b3}', "3"), ('\u{b4}', "'"), ('\u{b5}', "u"), ('\u{b7}', "."),
+    ('\u{b8}', ","), ('\u{b9}', "1"), ('\u{ba}', "o"), ('\u{bb}', ">>"), ('\u{bc}', "1/4"),
+    ('\u{bd}', "1/2"), ('\u{be}', "3/4"), ('\u{bf}', "?"), ('\u{c0}', "A"), ('\u{c1}', "A"),
+    ('\u{c2}', "A"), ('\u{c3}', "A"), ('\u{c4}', "Ae"), ('\u{c5}', "A"), ('\u{c6}', "AE"),
+    ('\u{c7}', "C"), ('\u{c8}', "E"), ('\u{c9}', "E"), ('\u{ca}', "E"), ('\u{cb}', "E"),
+    ('\u{cc}', "I"), ('\u{cd}', "I"), ('\u{ce}', "I"), ('\u{cf}', "I"), ('\u{d0}', "D"),
+    ('\u{d1}', "N"), ('\u{d2}', "O"), ('\u{d3}', "O"), ('\u{d4}', "O"), ('\u{d5}', "O"),
+    ('\u{d6}', "Oe"), ('\u{d7}', "x"), ('\u{d8}', "O"), ('\u{d9}', "U"), ('\u{da}', "U"),
+    ('\u{db}', "U"), ('\u{dc}', "Ue"), ('\u{dd}', "Y"), ('\u{de}', "Th"), ('\u{df}', "ss"),
+    ('\u{e0}', "a"), ('\u{e1}', "a"), ('\u{e2}', "a"), ('\u{e3}', "a"), ('\u{e4}', "ae"),
+    ('\u{e5}', "a"), ('\u{e6}', "ae"), ('\u{e7}', "c"), ('\u{e8}', "e"), ('\u{e9}', "e"),
+    ('\u{ea}', "e"), ('\u{eb}', "e"), ('\u{ec}', "i"), ('\u{ed}', "i"), ('\u{ee}', "i"),
+    ('\u{ef}', "i"), ('\u{f0}', "d"), ('\u{f1}', "n"), ('\u{f2}', "o"), ('\u{f3}', "o"),
+    ('\u{f4}', "o"), ('\u{f5}', "o"), ('\u{f6}', "oe"), ('\u{f7}', "/"), ('\u{f8}', "o"),
+    ('\u{f9}', "u"), ('\u{fa}', "u"), ('\u{fb}', "u"), ('\u{fc}', "ue"), ('\u{fd}', "y"),
+    ('\u{fe}', "th"), ('\u{ff}', "y"), ('\u{100}', "A"), ('\u{101}', "a"), ('\u{102}', "A"),
+    ('\u{103}', "a"), ('\u{104}', "A"), ('\u{105}', "a"), ('\u{106}', "C"), ('\u{107}', "c"),
+    ('\u{108}', "C"), ('\u{109}', "c"), ('\u{10a}', "C"), ('\u{10b}', "c"), ('\u{10c}', "C"),
+    ('\u{10d}', "c"), ('\u{10e}', "D"), ('\u{10f}', "d"), ('\u{110}', "D"), ('\u{111}', "d"),
+    ('\u{112}', "E"), ('\u{113}', "e"), ('\u{114}', "E"), ('\u{115}', "e"), ('\u{116}', "E"),
+    ('\u{117}', "e"), ('\u{118}', "E"), ('\u{119}', "e"), ('\u{11a}', "E"), ('\u{11b}', "e"),
+    ('\u{11c}', "G"), ('\u{11d}', "g"), ('\u{11e}', "G"), ('\u{11f}', "g"), ('\u{120}', "G"),
+    ('\u{121}', "g"), ('\u{122}', "G"), ('\u{123}', "g"), ('\u{124}', "H"), ('\u{125}', "h"),
+    ('\u{126}', "H"), ('\u{127}', "h"), ('\u{128}', "I"), ('\u{129}', "i"), ('\u{12a}', "I"),
+    ('\u{12b}', "i"), ('\u{12c}', "I"), ('\u{12d}', "i"), ('\u{12e}', "I"), ('\u{12f}', "i"),
+    ('\u{130}', "I"), ('\u{131}', "i"), ('\u{132}', "IJ"), ('\u{133}', "ij"), ('\u{134}', "J"),
+    ('\u{135}', "j"), ('\u{136}', "K"), ('\u{137}', "k"), ('\u{138}', "k"), ('\u{139}', "L"),
+    ('\u{13a}', "l"), ('\u{13b}', "L"), ('\u{13c}', "l"), ('\u{13d}', "L"), ('\u{13e}', "l"),
+    ('\u{13f}', "L"), ('\u{140}', "l"), ('\u{141}', "L"), ('\u{142}', "l"), ('\u{143}', "N"),
+    ('\u{144}', "n"), ('\u{145}', "N"), ('\u{146}', "n"), ('\u{147}', "N"), ('\u{148}', "n"),
+    ('\u{149}', "'n"), ('\u{14a}', "N"), ('\u{14b}', "n"), ('\u{14c}', "O"), ('\u{14d}', "o"),
+    ('\u{14e}', "O"), ('\u{14f}', "o"), ('\u{150}', "O"), ('\u{151}', "o"), ('\u{152}', "OE"),
+    ('\u{153}', "oe"), ('\u{154}', "R"), ('\u{155}', "r"), ('\u{156}', "R"), ('\u{157}', "r"),
+    ('\u{158}', "R"), ('\u{159}', "r"), ('\u{15a}', "S"), ('\u{15b}', "s"), ('\u{15c}', "S"),
+    ('\u{15d}', "s"), ('\u{15e}', "S"), ('\u{15f}', "s"), ('\u{160}', "S"), ('\u{161}', "s"),
+    ('\u{162}', "T"), ('\u{163}', "t"), ('\u{164}', "T"), ('\u{165}', "t"), ('\u{166}', "T"),
+    ('\u{167}', "t"), ('\u{168}', "U"), ('\u{169}', "u"), ('\u{16a}', "U"), ('\u{16b}', "u"),
+    ('\u{16c}', "U"), ('\u{16d}', "u"), ('\u{16e}', "U"), ('\u{16f}', "u"), ('\u{170}', "U"),
+    ('\u{171}', "u"), ('\u{172}', "U"), ('\u{173}', "u"), ('\u{174}', "W"), ('\u{175}', "w"),
+    ('\u{176}', "Y"), ('\u{177}', "y"), ('\u{178}', "Y"), ('\u{179}', "Z"), ('\u{17a}', "z"),
+    ('\u{17b}', "Z"), ('\u{17c}', "z"), ('\u{17d}', "Z"), ('\u{17e}', "z"), ('\u{17f}', "s"),
+    ('\u{180}', "b"), ('\u{181}', "B"), ('\u{187}', "C"), ('\u{188}', "c"), ('\u{189}', "D"),
+    ('\u{18a}', "D"), ('\u{191}', "F"), ('\u{192}', "f"), ('\u{193}', "G"), ('\u{197}', "I"),
+    ('\u{198}', "K"), ('\u{199}', "k"), ('\u{19a}', "l"), ('\u{19d}', "N"), ('\u{19e}', "n"),
+    ('\u{1a0}', "O"), ('\u{1a1}', "o"), ('\u{1a4}', "P"), ('\u{1a5}', "p"), ('\u{1ab}', "t"),
+    ('\u{1ac}', "T"), ('\u{1ad}', "t"), ('\u{1ae}', "T"), ('\u{1af}', "U"), ('\u{1b0}', "u"),
+    ('\u{1b2}', "V"), ('\u{1b3}', "Y"), ('\u{1b4}', "y"), ('\u{1b5}', "Z"), ('\u{1b6}', "z"),
+    ('\u{1c4}', "DZ"), ('\u{1c5}', "Dz"), ('\u{1c6}', "dz"), ('\u{1c7}', "LJ"),
+    ('\u{1c8}', "Lj"), ('\u{1c9}', "lj"), ('\u{1ca}', "NJ"), ('\u{1cb}', "Nj"),
+    ('\u{1cc}', "nj"), ('\u{1cd}', "A"), ('\u{1ce}', "a"), ('\u{1cf}', "I"), ('\u{1d0}', "i"),
+    ('\u{1d1}', "O"), ('\u{1d2}', "o"), ('\u{1d3}', "U"), ('\u{1d4}', "u"), ('\u{1d5}', "U"),
+    ('\u{1d6}', "u"), ('\u{1d7}', "U"), ('\u{1d8}', "u"), ('\u{1d9}', "U"), ('\u{1da}', "u"),
+    ('\u{1db}', "U"), ('\u{1dc}', "u"), ('\u{1de}', "A"), ('\u{1df}', "a"), ('\u{1e0}', "A"),
+    ('\u{1e1}', "a"), ('\u{1e6}', "G"), ('\u{1e7}', "g"), ('\u{1e8}', "K"), ('\u{1e9}', "k"),
+    ('\u{1ea}', "O"), ('\u{1eb}', "o"), ('\u{1ec}', "O"), ('\u{1ed}', "o"), ('\u{1f0}', "j"),
+    ('\u{1f1}', "DZ"), ('\u{1f2}', "Dz"), ('\u{1f3}', "dz"), ('\u{1f4}', "G"),
+    ('\u{1f5}', "g"), ('\u{1f8}', "N"), ('\u{1f9}', "n"), ('\u{1fa}', "A"), ('\u{1fb}', "a"),
+    ('\u{200}', "A"), ('\u{201}', "a"), ('\u{202}', "A"), ('\u{203}', "a"), ('\u{204}', "E"),
+    ('\u{205}', "e"), ('\u{206}', "E"), ('\u{207}', "e"), ('\u{208}', "I"), ('\u{209}', "i"),
+    ('\u{20a}', "I"), ('\u{20b}', "i"), ('\u{20c}', "O"), ('\u{20d}', "o"), ('\u{20e}', "O"),
+    ('\u{20f}', "o"), ('\u{210}', "R"), ('\u{211}', "r"), ('\u{212}', "R"), ('\u{213}', "r"),
+    ('\u{214}', "U"), ('\u{215}', "u"), ('\u{216}', "U"), ('\u{217}', "u"), ('\u{218}', "S"),
+    ('\u{219}', "s"), ('\u{21a}', "T"), ('\u{21b}', "t"), ('\u{21e}', "H"), ('\u{21f}', "h"),
+    ('\u{226}', "A"), ('\u{227}', "a"), ('\u{228}', "E"), ('\u{229}', "e"), ('\u{22a}', "O"),
+    ('\u{22b}', "o"), ('\u{22c}', "O"), ('\u{22d}', "o"), ('\u{22e}', "O"), ('\u{22f}', "o"),
+    ('\u{230}', "O"), ('\u{231}', "o"), ('\u{232}', "Y"), ('\u{233}', "y"), ('\u{391}', "A"),
+    ('\u{392}', "B"), ('\u{393}', "G"), ('\u{394}', "D"), ('\u{395}', "E"), ('\u{396}', "Z"),
+    ('\u{397}', "I"), ('\u{398}', "Th"), ('\u{399}', "I"), ('\u{39a}', "K"), ('\u{39b}', "L"),
+    ('\u{39c}', "M"), ('\u{39d}', "N"), ('\u{39e}', "X"), ('\u{39f}', "O"), ('\u{3a0}', "P"),
+    ('\u{3a1}', "R"), ('\u{3a3}', "S"), ('\u{3a4}', "T"), ('\u{3a5}', "Y"), ('\u{3a6}', "F"),
+    ('\u{3a7}', "Ch"), ('\u{3a8}', "Ps"), ('\u{3a9}', "O"), ('\u{3b1}', "a"), ('\u{3b2}', "b"),
+    ('\u{3b3}', "g"), ('\u{3b4}', "d"), ('\u{3b5}', "e"), ('\u{3b6}', "z"), ('\u{3b7}', "i"),
+    ('\u{3b8}', "th"), ('\u{3b9}', "i"), ('\u{3ba}', "k"), ('\u{3bb}', "l"), ('\u{3bc}', "m"),
+    ('\u{3bd}', "n"), ('\u{3be}', "x"), ('\u{3bf}', "o"), ('\u{3c0}', "p"), ('\u{3c1}', "r"),
+    ('\u{3c2}', "s"), ('\u{3c3}', "s"), ('\u{3c4}', "t"), ('\u{3c5}', "y"), ('\u{3c6}', "f"),
+    ('\u{3c7}', "ch"), ('\u{3c8}', "ps"), ('\u{3c9}', "o"), ('\u{401}', "Yo"),
+    ('\u{410}', "A"), ('\u{411}', "B"), ('\u{412}', "V"), ('\u{413}', "G"), ('\u{414}', "D"),
+    ('\u{415}', "E"), ('\u{416}', "Zh"), ('\u{417}', "Z"), ('\u{418}', "I"), ('\u{419}', "Y"),
+    ('\u{41a}', "K"), ('\u{41b}', "L"), ('\u{41c}', "M"), ('\u{41d}', "N"), ('\u{41e}', "O"),
+    ('\u{41f}', "P"), ('\u{420}', "R"), ('\u{421}', "S"), ('\u{422}', "T"), ('\u{423}', "U"),
+    ('\u{424}', "F"), ('\u{425}', "Kh"), ('\u{426}', "Ts"), ('\u{427}', "Ch"),
+    ('\u{428}', "Sh"), ('\u{429}', "Shch"), ('\u{42a}', ""), ('\u{42b}', "Y"), ('\u{42c}', ""),
+    ('\u{42d}', "E"), ('\u{42e}', "Yu"), ('\u{42f}', "Ya"), ('\u{430}', "a"), ('\u{431}', "b"),
+    ('\u{432}', "v"), ('\u{433}', "g"), ('\u{434}', "d"), ('\u{435}', "e"), ('\u{436}', "zh"),
+    ('\u{437}', "z"), ('\u{438}', "i"), ('\u{439}', "y"), ('\u{43a}', "k"), ('\u{43b}', "l"),
+    ('\u{43c}', "m"), ('\u{43d}', "n"), ('\u{43e}', "o"), ('\u{43f}', "p"), ('\u{440}', "r"),
+    ('\u{441}', "s"), ('\u{442}', "t"), ('\u{443}', "u"), ('\u{444}', "f"), ('\u{445}', "kh"),
+    ('\u{446}', "ts"), ('\u{447}', "ch"), ('\u{448}', "sh"), ('\u{449}', "shch"),
+    ('\u{44a}', ""), ('\u{44b}', "y"), ('\u{44c}', ""), ('\u{44d}', "e"), ('\u{44e}', "yu"),
+    ('\u{44f}', "ya"), ('\u{451}', "yo"), ('\u{1e00}', "A"), ('\u{1e01}', "a"),
+    ('\u{1e02}', "B"), ('\u{1e03}', "b"), ('\u{1e04}', "B"), ('\u{1e05}', "b"),
+    ('\u{1e06}', "B"), ('\u{1e07}', "b"), ('\u{1e08}', "C"), ('\u{1e09}', "c"),
+    ('\u{1e0a}', "D"), ('\u{1e0b}', "d"), ('\u{1e0c}', "D"), ('\u{1e0d}', "d"),
+    ('\u{1e0e}', "D"), ('\u{1e0f}', "d"), ('\u{1e10}', "D"), ('\u{1e11}', "d"),
+    ('\u{1e12}', "D"), ('\u{1e13}', "d"), ('\u{1e14}', "E"), ('\u{1e15}', "e"),
+    ('\u{1e16}', "E"), ('\u{1e17}', "e"), ('\u{1e18}', "E"), ('\u{1e19}', "e"),
+    ('\u{1e1a}', "E"), ('\u{1e1b}', "e"), ('\u{1e1c}', "E"), ('\u{1e1d}', "e"),
+    ('\u{1e1e}', "F"), ('\u{1e1f}', "f"), ('\u{1e20}', "G"), ('\u{1e21}', "g"),
+    ('\u{1e22}', "H"), ('\u{1e23}', "h"), ('\u{1e24}', "H"), ('\u{1e25}', "h"),
+    ('\u{1e26}', "H"), ('\u{1e27}', "h"), ('\u{1e28}', "H"), ('\u{1e29}', "h"),
+    ('\u{1e2a}', "H"), ('\u{1e2b}', "h"), ('\u{1e2c}', "I"), ('\u{1e2d}', "i"),
+    ('\u{1e2e}', "I"), ('\u{1e2f}', "i"), ('\u{1e30}', "K"), ('\u{1e31}', "k"),
+    ('\u{1e32}', "K"), ('\u{1e33}', "k"), ('\u{1e34}', "K"), ('\u{1e35}', "k"),
+    ('\u{1e36}', "L"), ('\u{1e37}', "l"), ('\u{1e38}', "L"), ('\u{1e39}', "l"),
+    ('\u{1e3a}', "L"), ('\u{1e3b}', "l"), ('\u{1e3c}', "L"), ('\u{1e3d}', "l"),
+    ('\u{1e3e}', "M"), ('\u{1e3f}', "m"), ('\u{1e40}', "M"), ('\u{1e41}', "m"),
+    ('\u{1e42}', "M"), ('\u{1e43}', "m"), ('\u{1e44}', "N"), ('\u{1e45}', "n"),
+    ('\u{1e46}', "N"), ('\u{1e47}', "n"), ('\u{1e48}', "N"), ('\u{1e49}', "n"),
+    ('\u{1e4a}', "N"), ('\u{1e4b}', "n"), ('\u{1e4c}', "O"), ('\u{1e4d}', "o"),
+    ('\u{1e4e}', "O"), ('\u{1e4f}', "o"), ('\u{1e50}', "O"), ('\u{1e51}', "o"),
+    ('\u{1e52}', "O"), ('\u{1e53}', "o"), ('\u{1e54}', "P"), ('\u{1e55}', "p"),
+    ('\u{1e56}', "P"), ('\u{1e57}', "p"), ('\u{1e58}', "R"), ('\u{1e59}', "r"),
+    ('\u{1e5a}', "R"), ('\u{1e5b}', "r"), ('\u{1e5c}', "R"), ('\u{1e5d}', "r"),
+    ('\u{1e5e}', "R"), ('\u{1e5f}', "r"), ('\u{1e60}', "S"), ('\u{1e61}', "s"),
+    ('\u{1e62}', "S"), ('\u{1e63}', "s"), ('\u{1e64}', "S"), ('\u{1e65}', "s"),
+    ('\u{1e66}', "S"), ('\u{1e67}', "s"), ('\u{1e68}', "S"), ('\u{1e69}', "s"),
+    ('\u{1e6a}', "T"), ('\u{1e6b}', "t"), ('\u{1e6c}', "T"), ('\u{1e6d}', "t"),
+    ('\u{1e6e}', "T"), ('\u{1e6f}', "t"), ('\u{1e70}', "T"), ('\u{1e71}', "t"),
+    ('\u{1e72}', "U"), ('\u{1e73}', "u"), ('\u{1e74}', "U"), ('\u{1e75}', "u"),
+    ('\u{1e76}', "U"), ('\u{1e77}', "u"), ('\u{1e78}', "U"), ('\u{1e79}', "u"),
+    ('\u{1e7a}', "U"), ('\u{1e7b}', "u"), ('\u{1e7c}', "V"), ('\u{1e7d}', "v"),
+    ('\u{1e7e}', "V"), ('\u{1e7f}', "v"), ('\u{1e80}', "W"), ('\u{1e81}', "w"),
+    ('\u{1e82}', "W"), ('\u{1e83}', "w"), ('\u{1e84}', "W"), ('\u{1e85}', "w"),
+    ('\u{1e86}', "W"), ('\u{1e87}', "w"), ('\u{1e88}', "W"), ('\u{1e89}', "w"),
+    ('\u{1e8a}', "X"), ('\u{1e8b}', "x"), ('\u{1e8c}', "X"), ('\u{1e8d}', "x"),
+    ('\u{1e8e}', "Y"), ('\u{1e8f}', "y"), ('\u{1e90}', "Z"), ('\u{1e91}', "z"),
+    ('\u{1e92}', "Z"), ('\u{1e93}', "z"), ('\u{1e94}', "Z"), ('\u{1e95}', "z"),
+    ('\u{1e96}', "h"), ('\u{1e97}', "t"), ('\u{1e98}', "w"), ('\u{1e99}', "y"),
+    ('\u{1e9b}', "s"), ('\u{1e9e}', "SS"), ('\u{1ea0}', "A"), ('\u{1ea1}', "a"),
+    ('\u{1ea2}', "A"), ('\u{1ea3}', "a"), ('\u{1ea4}', "A"), ('\u{1ea5}', "a"),
+    ('\u{1ea6}', "A"), ('\u{1ea7}', "a"), ('\u{1ea8}', "A"), ('\u{1ea9}', "a"),
+    ('\u{1eaa}', "A"), ('\u{1eab}', "a"), ('\u{1eac}', "A"), ('\u{1ead}', "a"),
+    ('\u{1eae}', "A"), ('\u{1eaf}', "a"), ('\u{1eb0}', "A"), ('\u{1eb1}', "a"),
+    ('\u{1eb2}', "A"), ('\u{1eb3}', "a"), ('\u{1eb4}', "A"), ('\u{1eb5}', "a"),
+    ('\u{1eb6}', "A"), ('\u{1eb7}', "a"), ('\u{1eb8}', "E"), ('\u{1eb9}', "e"),
+    ('\u{1eba}', "E"), ('\u{1ebb}', "e"), ('\u{1ebc}', "E"), ('\u{1ebd}', "e"),
+    ('\u{1ebe}', "E"), ('\u{1ebf}', "e"), ('\u{1ec0}', "E"), ('\u{1ec1}', "e"),
+    ('\u{1ec2}', "E"), ('\u{1ec3}', "e"), ('\u{1ec4}', "E"), ('\u{1ec5}', "e"),
+    ('\u{1ec6}', "E"), ('\u{1ec7}', "e"), ('\u{1ec8}', "I"), ('\u{1ec9}', "i"),
+    ('\u{1eca}', "I"), ('\u{1ecb}', "i"), ('\u{1ecc}', "O"), ('\u{1ecd}', "o"),
+    ('\u{1ece}', "O"), ('\u{1ecf}', "o"), ('\u{1ed0}', "O"), ('\u{1ed1}', "o"),
+    ('\u{1ed2}', "O"), ('\u{1ed3}', "o"), ('\u{1ed4}', "O"), ('\u{1ed5}', "o"),
+    ('\u{1ed6}', "O"), ('\u{1ed7}', "o"), ('\u{1ed8}', "O"), ('\u{1ed9}', "o"),
+    ('\u{1eda}', "O"), ('\u{1edb}', "o"), ('\u{1edc}', "O"), ('\u{1edd}', "o"),
+    ('\u{1ede}', "O"), ('\u{1edf}', "o"), ('\u{1ee0}', "O"), ('\u{1ee1}', "o"),
+    ('\u{1ee2}', "O"), ('\u{1ee3}', "o"), ('\u{1ee4}', "U"), ('\u{1ee5}', "u"),
+    ('\u{1ee6}', "U"), ('\u{1ee7}', "u"), ('\u{1ee8}', "U"), ('\u{1ee9}', "u"),
+    ('\u{1eea}', "U"), ('\u{1eeb}', "u"), ('\u{1eec}', "U"), ('\u{1eed}', "u"),
+    ('\u{1eee}', "U"), ('\u{1eef}', "u"), ('\u{1ef0}', "U"), ('\u{1ef1}', "u"),
+    ('\u{1ef2}', "Y"), ('\u{1ef3}', "y"), ('\u{1ef4}', "Y"), ('\u{1ef5}', "y"),
+    ('\u{1ef6}', "Y"), ('\u{1ef7}', "y"), ('\u{1ef8}', "Y"), ('\u{1ef9}', "y"),
+    ('\u{2000}', " "), ('\u{2001}', " "), ('\u{2002}', " "), ('\u{2003}', " "),
+    ('\u{2004}', " "), ('\u{2005}', " "), ('\u{2006}', " "), ('\u{2007}', " "),
+    ('\u{2008}', " "), ('\u{2009}', " "), ('\u{200a}', " "), ('\u{200b}', ""),
+    ('\u{200c}', ""), ('\u{200d}', ""), ('\u{2010}', "-"), ('\u{2011}', "-"),
+    ('\u{2012}', "-"), ('\u{2013}', "-"), ('\u{2014}', "--"), ('\u{2015}', "--"),
+    ('\u{2016}', "||"), ('\u{2018}', "'"), ('\u{2019}', "'"), ('\u{201a}', ","),
+    ('\u{201b}', "'"), ('\u{201c}', "\""), ('\u{201d}', "\""), ('\u{201e}', "\""),
+    ('\u{201f}', "\""), ('\u{2020}', "+"), ('\u{2021}', "++"), ('\u{2022}', "*"),
+    ('\u{2023}', ">"), ('\u{2024}', "."), ('\u{2025}', ".."), ('\u{2026}', "..."),
+    ('\u{2027}', "-"), ('\u{2030}', "%o"), ('\u{2032}', "'"), ('\u{2033}', "\""),
+    ('\u{2034}', "'''"), ('\u{2035}', "`"), ('\u{2039}', "<"), ('\u{203a}', ">"),
+    ('\u{203c}', "!!"), ('\u{2043}', "-"), ('\u{2044}', "/"), ('\u{2047}', "??"),
+    ('\u{2048}', "?!"), ('\u{2049}', "!?"), ('\u{2060}', ""), ('\u{2070}', "0"),
+    ('\u{2071}', "i"), ('\u{2074}', "4"), ('\u{2075}', "5"), ('\u{2076}', "6"),
+    ('\u{2077}', "7"), ('\u{2078}', "8"), ('\u{2079}', "9"), ('\u{207a}', "+"),
+    ('\u{207c}', "="), ('\u{207d}', "("), ('\u{207e}', ")"), ('\u{207f}', "n"),
+    ('\u{2080}', "0"), ('\u{2081}', "1"), ('\u{2082}', "2"), ('\u{2083}', "3"),
+    ('\u{2084}', "4"), ('\u{2085}', "5"), ('\u{2086}', "6"), ('\u{2087}', "7"),
+    ('\u{2088}', "8"), ('\u{2089}', "9"), ('\u{208a}', "+"), ('\u{208c}', "="),
+    ('\u{208d}', "("), ('\u{208e}', ")"), ('\u{2090}', "a"), ('\u{2091}', "e"),
+    ('\u{2092}', "o"), ('\u{2093}', "x"), ('\u{2095}', "h"), ('\u{2096}', "k"),
+    ('\u{2097}', "l"), ('\u{2098}', "m"), ('\u{2099}', "n"), ('\u{209a}', "p"),
+    ('\u{209b}', "s"), ('\u{209c}', "t"), ('\u{20a0}', "ECU"), ('\u{20a3}', "FF"),
+    ('\u{20a4}', "L"), ('\u{20a6}', "N"), ('\u{20a9}', "W"), ('\u{20aa}', "NS"),
+    ('\u{20ab}', "d"), ('\u{20ac}', "EUR"), ('\u{20b9}', "INR"), ('\u{20bd}', "RUB"),
+    ('\u{20bf}', "BTC"), ('\u{2100}', "a/c"), ('\u{2101}', "a/s"), ('\u{2102}', "C"),
+    ('\u{2103}', "C"), ('\u{2105}', "c/o"), ('\u{2106}', "c/u"), ('\u{2109}', "F"),
+    ('\u{210a}', "g"), ('\u{210b}', "H"), ('\u{210c}', "H"), ('\u{210d}', "H"),
+    ('\u{210e}', "h"), ('\u{2110}', "I"), ('\u{2111}', "I"), ('\u{2112}', "L"),
+    ('\u{2113}', "l"), ('\u{2115}', "N"), ('\u{2116}', "No"), ('\u{2119}', "P"),
+    ('\u{211a}', "Q"), ('\u{211b}', "R"), ('\u{211c}', "R"), ('\u{211d}', "R"),
+    ('\u{2120}', "SM"), ('\u{2121}', "TEL"), ('\u{2122}', "TM"), ('\u{2124}', "Z"),
+    ('\u{2128}', "Z"), ('\u{212a}', "K"), ('\u{212b}', "A"), ('\u{212c}', "B"),
+    ('\u{212d}', "C"), ('\u{212f}', "e"), ('\u{2130}', "E"), ('\u{2131}', "F"),
+    ('\u{2133}', "M"), ('\u{2134}', "o"), ('\u{2139}', "i"), ('\u{213b}', "FAX"),
+    ('\u{2145}', "D"), ('\u{2146}', "d"), ('\u{2147}', "e"), ('\u{2148}', "i"),
+    ('\u{2149}', "j"), ('\u{2160}', "I"), ('\u{2161}', "II"), ('\u{2162}', "III"),
+    ('\u{2163}', "IV"), ('\u{2164}', "V"), ('\u{2165}', "VI"), ('\u{2166}', "VII"),
+    ('\u{2167}', "VIII"), ('\u{2168}', "IX"), ('\u{2169}', "X"), ('\u{216a}', "XI"),
+    ('\u{216b}', "XII"), ('\u{216c}', "L"), ('\u{216d}', "C"), ('\u{216e}', "D"),
+    ('\u{216f}', "M"), ('\u{2170}', "i"), ('\u{2171}', "ii"), ('\u{2172}', "iii"),
+    ('\u{2173}', "iv"), ('\u{2174}', "v"), ('\u{2175}', "vi"), ('\u{2176}', "vii"),
+    ('\u{2177}', "viii"), ('\u{2178}', "ix"), ('\u{2179}', "x"), ('\u{217a}', "xi"),
+    ('\u{217b}', "xii"), ('\u{217c}', "l"), ('\u{217d}', "c"), ('\u{217e}', "d"),
+    ('\u{217f}', "m"), ('\u{2190}', "<-"), ('\u{2192}', "->"), ('\u{2194}', "<->"),
+    ('\u{21d0}', "<="), ('\u{21d2}', "=>"), ('\u{21d4}', "<=>"), ('\u{2212}', "-"),
+    ('\u{2215}', "/"), ('\u{2216}', "\\"), ('\u{2217}', "*"), ('\u{2223}', "|"),
+    ('\u{2236}', ":"), ('\u{223c}', "~"), ('\u{2248}', "~"), ('\u{2260}', "!="),
+    ('\u{2264}', "<="), ('\u{2265}', ">="), ('\u{226a}', "<<"), ('\u{226b}', ">>"),
+    ('\u{2460}', "1"), ('\u{2461}', "2"), ('\u{2462}', "3"), ('\u{2463}', "4"),
+    ('\u{2464}', "5"), ('\u{2465}', "6"), ('\u{2466}', "7"), ('\u{2467}', "8"),
+    ('\u{2468}', "9"), ('\u{2469}', "10"), ('\u{246a}', "11"), ('\u{246b}', "12"),
+    ('\u{246c}', "13"), ('\u{246d}', "14"), ('\u{246e}', "15"), ('\u{246f}', "16"),
+    ('\u{2470}', "17"), ('\u{2471}', "18"), ('\u{2472}', "19"), ('\u{2473}', "20"),
+    ('\u{2474}', "(1)"), ('\u{2475}', "(2)"), ('\u{2476}', "(3)"), ('\u{2477}', "(4)"),
+    ('\u{2478}', "(5)"), ('\u{2479}', "(6)"), ('\u{247a}', "(7)"), ('\u{247b}', "(8)"),
+    ('\u{247c}', "(9)"), ('\u{247d}', "(10)"), ('\u{247e}', "(11)"), ('\u{247f}', "(12)"),
+    ('\u{2480}', "(13)"), ('\u{2481}', "(14)"), ('\u{2482}', "(15)"), ('\u{2483}', "(16)"),
+    ('\u{2484}', "(17)"), ('\u{2485}', "(18)"), ('\u{2486}', "(19)"), ('\u{2487}', "(20)"),
+    ('\u{2488}', "1."), ('\u{2489}', "2."), ('\u{248a}', "3."), ('\u{248b}', "4."),
+    ('\u{248c}', "5."), ('\u{248d}', "6."), ('\u{248e}', "7."), ('\u{248f}', "8."),
+    ('\u{2490}', "9."), ('\u{2491}', "10."), ('\u{2492}', "11."), ('\u{2493}', "12."),
+    ('\u{2494}', "13."), ('\u{2495}', "14."), ('\u{2496}', "15."), ('\u{2497}', "16."),
+    ('\u{2498}', "17."), ('\u{2499}', "18."), ('\u{249a}', "19."), ('\u{249b}', "20."),
+    ('\u{249c}', "(a)"), ('\u{249d}', "(b)"), ('\u{249e}', "(c)"), ('\u{249f}', "(d)"),
+    ('\u{24a0}', "(e)"), ('\u{24a1}', "(f)"), ('\u{24a2}', "(g)"), ('\u{24a3}', "(h)"),
+    ('\u{24a4}', "(i)"), ('\u{24a5}', "(j)"), ('\u{24a6}', "(k)"), ('\u{24a7}', "(l)"),
+    ('\u{24a8}', "(m)"), ('\u{24a9}', "(n)"), ('\u{24aa}', "(o)"), ('\u{24ab}', "(p)"),
+    ('\u{24ac}', "(q)"), ('\u{24ad}', "(r)"), ('\u{24ae}', "(s)"), ('\u{24af}', "(t)"),
+    ('\u{24b0}', "(u)"), ('\u{24b1}', "(v)"), ('\u{24b2}', "(w)"), ('\u{24b3}', "(x)"),
+    ('\u{24b4}', "(y)"), ('\u{24b5}', "(z)"), ('\u{24b6}', "A"), ('\u{24b7}', "B"),
+    ('\u{24b8}', "C"), ('\u{24b9}', "D"), ('\u{24ba}', "E"), ('\u{24bb}', "F"),
+    ('\u{24bc}', "G"), ('\u{24bd}', "H"), ('\u{24be}', "I"), ('\u{24bf}', "J"),
+    ('\u{24c0}', "K"), ('\u{24c1}', "L"), ('\u{24c2}', "M"), ('\u{24c3}', "N"),
+    ('\u{24c4}', "O"), ('\u{24c5}', "P"), ('\u{24c6}', "Q"), ('\u{24c7}', "R"),
+    ('\u{24c8}', "S"), ('\u{24c9}', "T"), ('\u{24ca}', "U"), ('\u{24cb}', "V"),
+    ('\u{24cc}', "W"), ('\u{24cd}', "X"), ('\u{24ce}', "Y"), ('\u{24cf}', "Z"),
+    ('\u{24d0}', "a"), ('\u{24d1}', "b"), ('\u{24d2}', "c"), ('\u{24d3}', "d"),
+    ('\u{24d4}', "e"), ('\u{24d5}', "f"), ('\u{24d6}', "g"), ('\u{24d7}', "h"),
+    ('\u{24d8}', "i"), ('\u{24d9}', "j"), ('\u{24da}', "k"), ('\u{24db}', "l"),
+    ('\u{24dc}', "m"), ('\u{24dd}', "n"), ('\u{24de}', "o"), ('\u{24df}', "p"),
+    ('\u{24e0}', "q"), ('\u{24e1}', "r"), ('\u{24e2}', "s"), ('\u{24e3}', "t"),
+    ('\u{24e4}', "u"), ('\u{24e5}', "v"), ('\u{24e6}', "w"), ('\u{24e7}', "x"),
+    ('\u{24e8}', "y"), ('\u{24e9}', "z"), ('\u{24ea}', "0"), ('\u{2500}', "-"),
+    ('\u{2502}', "|"), ('\u{250c}', "+"), ('\u{2510}', "+"), ('\u{2514}', "+"),
+    ('\u{2518}', "+"), ('\u{251c}', "+"), ('\u{2524}', "+"), ('\u{252c}', "+"),
+    ('\u{2534}', "+"), ('\u{253c}', "+"), ('\u{3000}', " "), ('\u{fb00}', "ff"),
+    ('\u{fb01}', "fi"), ('\u{fb02}', "fl"), ('\u{fb03}', "ffi"), ('\u{fb04}', "ffl"),
+    ('\u{fb05}', "st"), ('\u{fb06}', "st"), ('\u{feff}', ""), ('\u{ff01}', "!"),
+    ('\u{ff02}', "\""), ('\u{ff03}', "#"), ('\u{ff04}', "$"), ('\u{ff05}', "%"),
+    ('\u{ff06}', "&"), ('\u{ff07}', "'"), ('\u{ff08}', "("), ('\u{ff09}', ")"),
+    ('\u{ff0a}', "*"), ('\u{ff0b}', "+"), ('\u{ff0c}', ","), ('\u{ff0d}', "-"),
+    ('\u{ff0e}', "."), ('\u{ff0f}', "/"), ('\u{ff10}', "0"), ('\u{ff11}', "1"),
+    ('\u{ff12}', "2"), ('\u{ff13}', "3"), ('\u{ff14}', "4"), ('\u{ff15}', "5"),
+    ('\u{ff16}', "6"), ('\u{ff17}', "7"), ('\u{ff18}', "8"), ('\u{ff19}', "9"),
+    ('\u{ff1a}', ":"), ('\u{ff1b}', ";"), ('\u{ff1c}', "<"), ('\u{ff1d}', "="),
+    ('\u{ff1e}', ">"), ('\u{ff1f}', "?"), ('\u{ff20}', "@"), ('\u{ff21}', "A"),
+    ('\u{ff22}', "B"), ('\u{ff23}', "C"), ('\u{ff24}', "D"), ('\u{ff25}', "E"),
+    ('\u{ff26}', "F"), ('\u{ff27}', "G"), ('\u{ff28}', "H"), ('\u{ff29}', "I"),
+    ('\u{ff2a}', "J"), ('\u{ff2b}', "K"), ('\u{ff2c}', "L"), ('\u{ff2d}', "M"),
+    ('\u{ff2e}', "N"), ('\u{ff2f}', "O"), ('\u{ff30}', "P"), ('\u{ff31}', "Q"),
+    ('\u{ff32}', "R"), ('\u{ff33}', "S"), ('\u{ff34}', "T"), ('\u{ff35}', "U"),
+    ('\u{ff36}', "V"), ('\u{ff37}', "W"), ('\u{ff38}', "X"), ('\u{ff39}', "Y"),
+    ('\u{ff3a}', "Z"), ('\u{ff3b}', "["), ('\u{ff3c}', "\\"), ('\u{ff3d}', "]"),
+    ('\u{ff3e}', "^"), ('\u{ff3f}', "_"), ('\u{ff40}', "`"), ('\u{ff41}', "a"),
+    ('\u{ff42}', "b"), ('\u{ff43}', "c"), ('\u{ff44}', "d"), ('\u{ff45}', "e"),
+    ('\u{ff46}', "f"), ('\u{ff47}', "g"), ('\u{ff48}', "h"), ('\u{ff49}', "i"),
+    ('\u{ff4a}', "j"), ('\u{ff4b}', "k"), ('\u{ff4c}', "l"), ('\u{ff4d}', "m"),
+    ('\u{ff4e}', "n"), ('\u{ff4f}', "o"), ('\u{ff50}', "p"), ('\u{ff51}', "q"),
+    ('\u{ff52}', "r"), ('\u{ff53}', "s"), ('\u{ff54}', "t"), ('\u{ff55}', "u"),
+    ('\u{ff56}', "v"), ('\u{ff57}', "w"), ('\u{ff58}', "x"), ('\u{ff59}', "y"),
+    ('\u{ff5a}', "z"), ('\u{ff5b}', "{"), ('\u{ff5c}', "|"), ('\u{ff5d}', "}"),
+    ('\u{ff5e}', "~"),
+];
@@ -0,0 +1,434 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use alloc::borrow::{Borrow, ToOwned};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use case;
+use soft_str::SoftAsciiStr;
+#[cfg(feature = "alloc")]
+use soft_string::SoftAsciiString;
+
+/// a `SoftAsciiStr` which is compared, ordered and hashed ignoring ascii case
+///
+/// This is meant for case-insensitive identifiers like header names, SMTP
+/// verbs or MIME parameter names. Only `A`-`Z`/`a`-`z` are folded, other
+/// chars (including non us-ascii ones) are compared as they are.
+///
+/// As `SoftAsciiUncasedString` implements `Borrow<SoftAsciiUncasedStr>`
+/// a map using it as key can be queried with a `&SoftAsciiUncasedStr`
+/// created from any `&SoftAsciiStr` without allocating.
+#[derive(Debug)]
+// `repr(transparent)` is required for the cast in `new`
+#[repr(transparent)]
+pub struct SoftAsciiUncasedStr(SoftAsciiStr);
+
+impl SoftAsciiUncasedStr {
+
+    #[inline(always)]
+    pub fn new(s: &SoftAsciiStr) -> &SoftAsciiUncasedStr {
+        unsafe { &*(s as *const SoftAsciiStr as *const SoftAsciiUncasedStr) }
+    }
+
+    #[inline(always)]
+    #[cfg_attr(any(feature = "check-unchecked", feature = "track-unchecked", debug_assertions), track_caller)]
+    pub fn from_unchecked(s: &str) -> &SoftAsciiUncasedStr {
+        SoftAsciiUncasedStr::new(SoftAsciiStr::from_unchecked(s))
+    }
+
+    #[inline(always)]
+    pub fn as_soft_ascii_str(&self) -> &SoftAsciiStr {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Deref for SoftAsciiUncasedStr {
+    type Target = SoftAsciiStr;
+
+    #[inline(always)]
+    fn deref(&self) -> &SoftAsciiStr {
+        &self.0
+    }
+}
+
+impl PartialEq for SoftAsciiUncasedStr {
+    #[inline]
+    fn eq(&self, other: &SoftAsciiUncasedStr) -> bool {
+        case::eq_ignore_case(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl Eq for SoftAsciiUncasedStr {}
+
+impl PartialEq<SoftAsciiStr> for SoftAsciiUncasedStr {
+    #[inline]
+    fn eq(&self, other: &SoftAsciiStr) -> bool {
+        case::eq_ignore_case(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl PartialEq<str> for SoftAsciiUncasedStr {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        case::eq_ignore_case(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl<'a> PartialEq<&'a str> for SoftAsciiUncasedStr {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        case::eq_ignore_case(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl PartialOrd for SoftAsciiUncasedStr {
+    #[inline]
+    fn partial_cmp(&self, other: &SoftAsciiUncasedStr) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SoftAsciiUncasedStr {
+    /// compares the lowercase forms
+    fn cmp(&self, other: &SoftAsciiUncasedStr) -> Ordering {
+        let left = self.as_bytes().iter().map(u8::to_ascii_lowercase);
+        let right = other.as_bytes().iter().map(u8::to_ascii_lowercase);
+        left.cmp(right)
+    }
+}
+
+impl Hash for SoftAsciiUncasedStr {
+    /// hashes the lowercase form, like `str` with a trailing `0xff`
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut buf = [0u8; 64];
+        for chunk in self.as_bytes().chunks(buf.len()) {
+            let buf = &mut buf[..chunk.len()];
+            buf.copy_from_slice(chunk);
+            case::make_lowercase(buf);
+            state.write(buf);
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl Display for SoftAsciiUncasedStr {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, fter)
+    }
+}
+
+impl AsRef<SoftAsciiUncasedStr> for SoftAsciiUncasedStr {
+    #[inline(always)]
+    fn as_ref(&self) -> &SoftAsciiUncasedStr {
+        self
+    }
+}
+
+impl AsRef<SoftAsciiStr> for SoftAsciiUncasedStr {
+    #[inline(always)]
+    fn as_ref(&self) -> &SoftAsciiStr {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SoftAsciiUncasedStr {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'a> From<&'a SoftAsciiStr> for &'a SoftAsciiUncasedStr {
+    #[inline(always)]
+    fn from(s: &'a SoftAsciiStr) -> &'a SoftAsciiUncasedStr {
+        SoftAsciiUncasedStr::new(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToOwned for SoftAsciiUncasedStr {
+    type Owned = SoftAsciiUncasedString;
+
+    fn to_owned(&self) -> SoftAsciiUncasedString {
+        SoftAsciiUncasedString(self.0.to_owned())
+    }
+}
+
+/// a `SoftAsciiString` which is compared, ordered and hashed ignoring ascii case
+///
+/// See `SoftAsciiUncasedStr`, it's the owned version of it (and does not
+/// implement `Borrow<SoftAsciiStr>`/`Borrow<str>`, as their `Hash`/`Eq`
+/// implementations are case sensitive).
+///
+/// ```
+/// use std::collections::HashMap;
+/// use soft_ascii_string::{SoftAsciiStr, SoftAsciiUncasedStr, SoftAsciiUncasedString};
+///
+/// let mut headers = HashMap::new();
+/// headers.insert(SoftAsciiUncasedString::from_unchecked("Content-Type"), "text/plain");
+///
+/// let name = SoftAsciiStr::from_unchecked("content-type");
+/// assert_eq!(headers.get(SoftAsciiUncasedStr::new(name)), Some(&"text/plain"));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct SoftAsciiUncasedString(SoftAsciiString);
+
+#[cfg(feature = "alloc")]
+impl SoftAsciiUncasedString {
+
+    #[inline(always)]
+    pub fn new(s: SoftAsciiString) -> SoftAsciiUncasedString {
+        SoftAsciiUncasedString(s)
+    }
+
+    #[inline(always)]
+    #[cfg_attr(any(feature = "check-unchecked", feature = "track-unchecked", debug_assertions), track_caller)]
+    pub fn from_unchecked<S: Into<String>>(s: S) -> SoftAsciiUncasedString {
+        SoftAsciiUncasedString(SoftAsciiString::from_unchecked(s))
+    }
+
+    #[inline(always)]
+    pub fn as_uncased_str(&self) -> &SoftAsciiUncasedStr {
+        SoftAsciiUncasedStr::new(&self.0)
+    }
+
+    #[inline(always)]
+    pub fn into_soft_ascii_string(self) -> SoftAsciiString {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Deref for SoftAsciiUncasedString {
+    type Target = SoftAsciiUncasedStr;
+
+    #[inline(always)]
+    fn deref(&self) -> &SoftAsciiUncasedStr {
+        self.as_uncased_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Borrow<SoftAsciiUncasedStr> for SoftAsciiUncasedString {
+    #[inline(always)]
+    fn borrow(&self) -> &SoftAsciiUncasedStr {
+        self.as_uncased_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq for SoftAsciiUncasedString {
+    #[inline]
+    fn eq(&self, other: &SoftAsciiUncasedString) -> bool {
+        self.as_uncased_str() == other.as_uncased_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Eq for SoftAsciiUncasedString {}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<SoftAsciiUncasedStr> for SoftAsciiUncasedString {
+    #[inline]
+    fn eq(&self, other: &SoftAsciiUncasedStr) -> bool {
+        self.as_uncased_str() == other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> PartialEq<&'a SoftAsciiUncasedStr> for SoftAsciiUncasedString {
+    #[inline]
+    fn eq(&self, other: &&'a SoftAsciiUncasedStr) -> bool {
+        self.as_uncased_str() == *other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<str> for SoftAsciiUncasedString {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_uncased_str() == other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> PartialEq<&'a str> for SoftAsciiUncasedString {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_uncased_str() == *other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialOrd for SoftAsciiUncasedString {
+    #[inline]
+    fn partial_cmp(&self, other: &SoftAsciiUncasedString) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Ord for SoftAsciiUncasedString {
+    #[inline]
+    fn cmp(&self, other: &SoftAsciiUncasedString) -> Ordering {
+        self.as_uncased_str().cmp(other.as_uncased_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Hash for SoftAsciiUncasedString {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // has to be the same as for the borrowed form
+        self.as_uncased_str().hash(state)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for SoftAsciiUncasedString {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, fter)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<SoftAsciiUncasedStr> for SoftAsciiUncasedString {
+    #[inline(always)]
+    fn as_ref(&self) -> &SoftAsciiUncasedStr {
+        self.as_uncased_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<SoftAsciiStr> for SoftAsciiUncasedString {
+    #[inline(always)]
+    fn as_ref(&self) -> &SoftAsciiStr {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<str> for SoftAsciiUncasedString {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<SoftAsciiString> for SoftAsciiUncasedString {
+    #[inline(always)]
+    fn from(s: SoftAsciiString) -> SoftAsciiUncasedString {
+        SoftAsciiUncasedString(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<SoftAsciiUncasedString> for SoftAsciiString {
+    #[inline(always)]
+    fn from(s: SoftAsciiUncasedString) -> SoftAsciiString {
+        s.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a SoftAsciiStr> for SoftAsciiUncasedString {
+    #[inline]
+    fn from(s: &'a SoftAsciiStr) -> SoftAsciiUncasedString {
+        SoftAsciiUncasedString(s.to_owned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a SoftAsciiUncasedStr> for SoftAsciiUncasedString {
+    #[inline]
+    fn from(s: &'a SoftAsciiUncasedStr) -> SoftAsciiUncasedString {
+        s.to_owned()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use std::collections::{HashMap, BTreeMap};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::vec::Vec;
+    use ::{SoftAsciiStr, SoftAsciiString};
+    use super::{SoftAsciiUncasedStr, SoftAsciiUncasedString};
+
+    fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn eq_folds_ascii_case() {
+        let upper = SoftAsciiUncasedStr::from_unchecked("MAIL FROM");
+        let lower = SoftAsciiUncasedStr::from_unchecked("mail from");
+        assert_eq!(upper, lower);
+        assert_eq!(upper, "Mail From");
+        assert_ne!(upper, SoftAsciiUncasedStr::from_unchecked("mail_from"));
+        assert_eq!(SoftAsciiUncasedString::from_unchecked("Content-Type"), "CONTENT-TYPE");
+    }
+
+    #[test]
+    fn hash_folds_ascii_case() {
+        let long = "X-Some-Long-Header-Name-Which-Is-Longer-Than-The-Hash-Buffer-Of-64-Bytes";
+        let owned = SoftAsciiUncasedString::from_unchecked(long);
+        let borrowed = SoftAsciiUncasedStr::from_unchecked(long.to_ascii_uppercase().leak());
+        assert_eq!(hash_of(&owned), hash_of(borrowed));
+        assert_eq!(hash_of(SoftAsciiUncasedStr::from_unchecked("Ab")),
+            hash_of(SoftAsciiUncasedStr::from_unchecked("aB")));
+        // like for str ("a", "b") and ("ab", "") differ
+        assert_ne!(
+            hash_of(&(SoftAsciiUncasedStr::from_unchecked("a"), SoftAsciiUncasedStr::from_unchecked("b"))),
+            hash_of(&(SoftAsciiUncasedStr::from_unchecked("ab"), SoftAsciiUncasedStr::from_unchecked(""))));
+    }
+
+    #[test]
+    fn ord_folds_ascii_case() {
+        let mut names: Vec<SoftAsciiUncasedString> = ["to", "From", "CC", "bcc", "_x"].iter()
+            .map(|&name| SoftAsciiUncasedString::from_unchecked(name))
+            .collect();
+        names.sort();
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        assert_eq!(names, vec!["_x", "bcc", "CC", "From", "to"]);
+    }
+
+    #[test]
+    fn lookup_with_borrowed_key() {
+        let mut hash_map = HashMap::new();
+        let mut btree_map = BTreeMap::new();
+        for &(name, value) in &[("Content-Type", 1), ("Subject", 2)] {
+            hash_map.insert(SoftAsciiUncasedString::from_unchecked(name), value);
+            btree_map.insert(SoftAsciiUncasedString::from_unchecked(name), value);
+        }
+        let key = SoftAsciiStr::from_unchecked("content-TYPE");
+        assert_eq!(hash_map.get(SoftAsciiUncasedStr::new(key)), Some(&1));
+        assert_eq!(btree_map.get(SoftAsciiUncasedStr::new(key)), Some(&1));
+        let key: &SoftAsciiUncasedStr = SoftAsciiStr::from_unchecked("SUBJECT").into();
+        assert_eq!(hash_map.get(key), Some(&2));
+        assert_eq!(btree_map.get(key), Some(&2));
+        assert_eq!(hash_map.get(SoftAsciiUncasedStr::from_unchecked("To")), None);
+    }
+
+    #[test]
+    fn conversions_keep_case() {
+        let string = SoftAsciiString::from_unchecked("Content-Type");
+        let uncased = SoftAsciiUncasedString::from(string);
+        assert_eq!(uncased.as_str(), "Content-Type");
+        assert_eq!(uncased.to_string(), "Content-Type");
+        let string: SoftAsciiString = uncased.into();
+        assert_eq!(string, "Content-Type");
+    }
+}
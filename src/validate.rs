@@ -0,0 +1,169 @@
+//! fast us-ascii validation used by all checked constructors
+//!
+//! On x86_64 16 (SSE2) or 64 (AVX2, two 32 byte vectors) bytes are
+//! checked at a time, other targets check a `u64` at a time. The
+//! first non us-ascii byte is then located within the failing block.
+
+/// inputs shorter than this are checked byte by byte
+const SCALAR_THRESHOLD: usize = 16;
+
+/// returns the index of the first non us-ascii byte
+#[inline]
+pub(crate) fn first_non_ascii(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() < SCALAR_THRESHOLD {
+        return scalar(bytes);
+    }
+    first_non_ascii_blocks(bytes)
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+fn first_non_ascii_blocks(bytes: &[u8]) -> Option<usize> {
+    if is_x86_feature_detected!("avx2") {
+        // Safe: the cpu supports avx2
+        unsafe { x86_64::avx2(bytes) }
+    } else {
+        x86_64::sse2(bytes)
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", not(feature = "std"), target_feature = "avx2"))]
+fn first_non_ascii_blocks(bytes: &[u8]) -> Option<usize> {
+    // Safe: the crate is compiled for cpus supporting avx2
+    unsafe { x86_64::avx2(bytes) }
+}
+
+#[cfg(all(target_arch = "x86_64", not(feature = "std"), not(target_feature = "avx2")))]
+fn first_non_ascii_blocks(bytes: &[u8]) -> Option<usize> {
+    x86_64::sse2(bytes)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn first_non_ascii_blocks(bytes: &[u8]) -> Option<usize> {
+    word_at_a_time(bytes)
+}
+
+fn scalar(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().position(|&b| b >= 0x80)
+}
+
+#[cfg_attr(target_arch = "x86_64", allow(dead_code))]
+fn word_at_a_time(bytes: &[u8]) -> Option<usize> {
+    const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+    let mut chunks = bytes.chunks_exact(8);
+    for (idx, chunk) in chunks.by_ref().enumerate() {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(chunk);
+        if u64::from_ne_bytes(word) & HIGH_BITS != 0 {
+            return scalar(chunk).map(|pos| idx * 8 + pos);
+        }
+    }
+    let offset = bytes.len() - chunks.remainder().len();
+    scalar(chunks.remainder()).map(|pos| offset + pos)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use core::arch::x86_64::{
+        __m128i, _mm_loadu_si128, _mm_movemask_epi8,
+        __m256i, _mm256_loadu_si256, _mm256_movemask_epi8, _mm256_or_si256
+    };
+    use super::scalar;
+
+    /// sse2 is part of the x86_64 baseline so this is always available
+    pub(super) fn sse2(bytes: &[u8]) -> Option<usize> {
+        let mut chunks = bytes.chunks_exact(16);
+        for (idx, chunk) in chunks.by_ref().enumerate() {
+            // Safe: the chunk is 16 bytes long and loadu has no alignment requirements
+            let mask = unsafe {
+                _mm_movemask_epi8(_mm_loadu_si128(chunk.as_ptr() as *const __m128i))
+            };
+            if mask != 0 {
+                return Some(idx * 16 + mask.trailing_zeros() as usize);
+            }
+        }
+        let offset = bytes.len() - chunks.remainder().len();
+        scalar(chunks.remainder()).map(|pos| offset + pos)
+    }
+
+    /// # Safety
+    ///
+    /// the cpu has to support avx2
+    #[cfg_attr(not(any(feature = "std", target_feature = "avx2")), allow(dead_code))]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn avx2(bytes: &[u8]) -> Option<usize> {
+        let mut chunks = bytes.chunks_exact(64);
+        for (idx, chunk) in chunks.by_ref().enumerate() {
+            // Safe: the chunk is 64 bytes long and loadu has no alignment requirements
+            let mask = unsafe {
+                let ptr = chunk.as_ptr() as *const __m256i;
+                let low = _mm256_loadu_si256(ptr);
+                let high = _mm256_loadu_si256(ptr.add(1));
+                _mm256_movemask_epi8(_mm256_or_si256(low, high))
+            };
+            if mask != 0 {
+                return sse2(chunk).map(|pos| idx * 64 + pos);
+            }
+        }
+        let offset = bytes.len() - chunks.remainder().len();
+        sse2(chunks.remainder()).map(|pos| offset + pos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{first_non_ascii, scalar, word_at_a_time};
+
+    /// checks all lengths up to 100 with a non ascii byte at every position and alignment
+    fn check_all_positions(find: fn(&[u8]) -> Option<usize>) {
+        let buf = [b'a'; 128];
+        for start in 0..8 {
+            for len in 0..100 {
+                let mut input = buf;
+                let slice = &input[start..start + len];
+                assert_eq!(find(slice), None, "start={} len={}", start, len);
+                for pos in 0..len {
+                    input[start + pos] = 0xc3;
+                    input[start + len - 1] = 0xa4;
+                    let slice = &input[start..start + len];
+                    assert_eq!(find(slice), Some(pos), "start={} len={} pos={}", start, len, pos);
+                    assert_eq!(find(slice), scalar(slice));
+                    input = buf;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dispatching() {
+        check_all_positions(first_non_ascii);
+    }
+
+    #[test]
+    fn portable() {
+        check_all_positions(word_at_a_time);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn sse2() {
+        check_all_positions(super::x86_64::sse2);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn avx2() {
+        if is_x86_feature_detected!("avx2") {
+            // Safe: the cpu supports avx2
+            check_all_positions(|bytes| unsafe { super::x86_64::avx2(bytes) });
+        }
+    }
+
+    #[test]
+    fn all_byte_values() {
+        for byte in 0..=255u8 {
+            let mut input = [b' '; 40];
+            input[33] = byte;
+            assert_eq!(first_non_ascii(&input).is_none(), byte < 0x80);
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! Reporting of soft constraint violations
+//!
+//! Every time `revalidate_soft_constraint` finds a non us-ascii char, a
+//! lossy constructor (`SoftAsciiStr::from_str_lossy`,
+//! `SoftAsciiString::from_string_lossy`) has to replace non us-ascii chars
+//! or `SoftAsciiString::sanitize` has to repair a string, the violation
+//! is counted and passed to the process wide hook (if one is set).
+//!
+//! Each call is counted once, independent of how many non us-ascii chars
+//! the value contains, the reported char and offset are the first ones.
+//!
+//! In debug builds (`debug_assertions`) with the `std` feature the unchecked
+//! constructors of `SoftAsciiStr` and `SoftAsciiString` record where they
+//! were called, so the hook can tell where the value breaking the soft
+//! constraint was created (`ViolationInfo::created_at`). This uses the side
+//! table of the `track` module (with the same limitations), the
+//! `track-unchecked` feature records it in release builds, too.
+//!
+//! # Example
+//!
+//! ```
+//! use soft_ascii_string::violation::{self, Trigger, ViolationInfo};
+//!
+//! fn log_violation(info: &ViolationInfo) {
+//!     eprintln!("soft constraint violated: {}", info);
+//! }
+//!
+//! violation::set_violation_hook(log_violation);
+//!
+//! // e.g. at shutdown
+//! println!("{} strings had to be sanitized", violation::violation_count(Trigger::Sanitize));
+//! ```
+use core::fmt::{self, Display};
+use core::mem;
+use core::panic::Location;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+#[cfg(any(feature = "track-unchecked", all(feature = "std", debug_assertions)))]
+use track;
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+static COUNTS: [AtomicUsize; 3] = [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)];
+
+/// the type whose soft constraint was violated
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    /// a `SoftAsciiChar`
+    SoftAsciiChar,
+    /// a `SoftAsciiStr` (including the input of `SoftAsciiStr::from_str_lossy`)
+    SoftAsciiStr,
+    /// a `SoftAsciiString` (including the input of `SoftAsciiString::from_string_lossy`)
+    SoftAsciiString
+}
+
+/// the operation which detected the violation
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    /// `revalidate_soft_constraint`
+    Revalidate,
+    /// `SoftAsciiStr::from_str_lossy`/`SoftAsciiString::from_string_lossy`
+    Lossy,
+    /// `SoftAsciiString::sanitize`
+    Sanitize
+}
+
+impl Trigger {
+    fn index(self) -> usize {
+        match self {
+            Trigger::Revalidate => 0,
+            Trigger::Lossy => 1,
+            Trigger::Sanitize => 2
+        }
+    }
+}
+
+/// information about a detected violation passed to the hook
+#[derive(Debug, Copy, Clone)]
+pub struct ViolationInfo {
+    value_type: ValueType,
+    trigger: Trigger,
+    invalid_char: char,
+    offset: usize,
+    detected_at: Option<&'static Location<'static>>,
+    created_at: Option<&'static Location<'static>>
+}
+
+impl ViolationInfo {
+
+    /// returns the type whose soft constraint was violated
+    pub fn value_type(&self) -> ValueType {
+        self.value_type
+    }
+
+    /// returns the operation which detected the violation
+    pub fn trigger(&self) -> Trigger {
+        self.trigger
+    }
+
+    /// returns the first non us-ascii char
+    pub fn invalid_char(&self) -> char {
+        self.invalid_char
+    }
+
+    /// returns the byte offset of the first non us-ascii char
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// returns the location of the call which detected the violation
+    ///
+    /// This is the caller of e.g. `revalidate_soft_constraint`, see
+    /// `created_at` for where the value was created. It's only recorded in
+    /// debug builds (`debug_assertions`), in release builds `None` is returned.
+    pub fn detected_at(&self) -> Option<&'static Location<'static>> {
+        self.detected_at
+    }
+
+    /// returns the location of the `from_unchecked` call which created the value
+    ///
+    /// It's recorded in debug builds with the `std` feature and with the
+    /// `track-unchecked` feature, else `None` is returned. It's also `None`
+    /// for `SoftAsciiChar` (a `Copy` value without an address), for the
+    /// lossy constructors (their input isn't a soft value) and for values
+    /// the `track` module can't find (e.g. sub-slices).
+    pub fn created_at(&self) -> Option<&'static Location<'static>> {
+        self.created_at
+    }
+}
+
+impl Display for ViolationInfo {
+    fn fmt(&self, fter: &mut fmt::Formatter) -> fmt::Result {
+        write!(fter, "{:?} contains non us-ascii char {:?} at byte offset {} (found by {:?}",
+            self.value_type, self.invalid_char, self.offset, self.trigger)?;
+        if let Some(location) = self.detected_at {
+            write!(fter, " at {}", location)?;
+        }
+        write!(fter, ")")?;
+        if let Some(location) = self.created_at {
+            write!(fter, ", created at {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+/// sets the process wide hook called for each detected violation
+///
+/// It replaces any previously set hook.
+pub fn set_violation_hook(hook: fn(&ViolationInfo)) {
+    HOOK.store(hook as *mut (), Ordering::SeqCst);
+}
+
+/// removes the process wide hook (if one is set)
+pub fn clear_violation_hook() {
+    HOOK.store(ptr::null_mut(), Ordering::SeqCst);
+}
+
+/// returns the process wide hook (if one is set)
+pub fn violation_hook() -> Option<fn(&ViolationInfo)> {
+    let hook = HOOK.load(Ordering::SeqCst);
+    if hook.is_null() {
+        None
+    } else {
+        // Safe: HOOK is only ever set to a `fn(&ViolationInfo)`
+        Some(unsafe { mem::transmute::<*mut (), fn(&ViolationInfo)>(hook) })
+    }
+}
+
+/// returns how many violations were detected by `trigger` (since the last reset)
+pub fn violation_count(trigger: Trigger) -> usize {
+    COUNTS[trigger.index()].load(Ordering::SeqCst)
+}
+
+/// returns how many violations were detected in total (since the last reset)
+pub fn total_violation_count() -> usize {
+    COUNTS.iter().map(|count| count.load(Ordering::SeqCst)).sum()
+}
+
+/// resets all violation counters to 0
+pub fn reset_violation_counts() {
+    for count in COUNTS.iter() {
+        count.store(0, Ordering::SeqCst);
+    }
+}
+
+/// counts the violation and calls the hook
+#[cfg_attr(debug_assertions, track_caller)]
+pub(crate) fn report(value_type: ValueType, trigger: Trigger, invalid_char: char, offset: usize) {
+    COUNTS[trigger.index()].fetch_add(1, Ordering::SeqCst);
+    if let Some(hook) = violation_hook() {
+        hook(&ViolationInfo {
+            value_type, trigger, invalid_char, offset,
+            detected_at: caller(),
+            created_at: None
+        });
+    }
+}
+
+/// like `report` for the (non us-ascii) char starting at `offset` in `source`
+#[cfg_attr(debug_assertions, track_caller)]
+pub(crate) fn report_in(value_type: ValueType, trigger: Trigger, source: &str, offset: usize) {
+    COUNTS[trigger.index()].fetch_add(1, Ordering::SeqCst);
+    if let Some(hook) = violation_hook() {
+        let invalid_char = source[offset..].chars().next()
+            .expect("[BUG] violation has to point to a char");
+        hook(&ViolationInfo {
+            value_type, trigger, invalid_char, offset,
+            detected_at: caller(),
+            created_at: match trigger {
+                Trigger::Lossy => None,
+                _ => lookup_creation(source, offset)
+            }
+        });
+    }
+}
+
+/// records that `value` was created by an unchecked constructor (see `ViolationInfo::created_at`)
+#[inline(always)]
+#[cfg_attr(any(feature = "track-unchecked", debug_assertions), track_caller)]
+pub(crate) fn record_creation(value: &str) {
+    #[cfg(any(feature = "track-unchecked", all(feature = "std", debug_assertions)))]
+    track::record(value, Location::caller());
+    #[cfg(not(any(feature = "track-unchecked", all(feature = "std", debug_assertions))))]
+    let _ = value;
+}
+
+#[cfg(any(feature = "track-unchecked", all(feature = "std", debug_assertions)))]
+fn lookup_creation(source: &str, offset: usize) -> Option<&'static Location<'static>> {
+    track::lookup(source, offset)
+}
+
+#[cfg(not(any(feature = "track-unchecked", all(feature = "std", debug_assertions))))]
+fn lookup_creation(_source: &str, _offset: usize) -> Option<&'static Location<'static>> {
+    None
+}
+
+#[inline(always)]
+#[cfg_attr(debug_assertions, track_caller)]
+fn caller() -> Option<&'static Location<'static>> {
+    if cfg!(debug_assertions) { Some(Location::caller()) } else { None }
+}
+
+#[cfg(all(test, feature = "alloc", not(feature = "check-unchecked")))]
+mod test {
+    use std::cell::RefCell;
+    use std::vec::Vec;
+    use ::{SoftAsciiChar, SoftAsciiStr, SoftAsciiString};
+    use super::*;
+
+    thread_local! {
+        // other tests run in parallel and might report violations, too
+        static RECORDED: RefCell<Option<Vec<ViolationInfo>>> = const { RefCell::new(None) };
+    }
+
+    fn record(info: &ViolationInfo) {
+        RECORDED.with(|recorded| {
+            if let Some(ref mut infos) = *recorded.borrow_mut() {
+                infos.push(*info);
+            }
+        })
+    }
+
+    // the hook and counters are process wide, so all paths are tested in one test
+    #[test]
+    fn hook_and_counters() {
+        set_violation_hook(record);
+        assert!(violation_hook().is_some());
+        let before = violation_count(Trigger::Sanitize);
+        RECORDED.with(|recorded| *recorded.borrow_mut() = Some(Vec::new()));
+
+        let _ = SoftAsciiChar::from_unchecked('↓').revalidate_soft_constraint();
+        let _ = SoftAsciiChar::from_unchecked('a').revalidate_soft_constraint();
+        let _ = SoftAsciiStr::from_unchecked("ab↓↓").revalidate_soft_constraint();
+        let _ = SoftAsciiStr::from_str_lossy("a↓");
+        let _ = SoftAsciiStr::from_str_lossy("abc");
+        let _ = SoftAsciiString::from_unchecked("a↓").revalidate_soft_constraint();
+        let _ = SoftAsciiString::from_string_lossy("abc↓", SoftAsciiChar::REPLACEMENT_CHARACTER);
+        let mut string = SoftAsciiString::from_unchecked("a↓b↓");
+        string.sanitize();
+        string.sanitize();
+
+        let infos = RECORDED.with(|recorded| recorded.borrow_mut().take()).unwrap();
+        let expected = [
+            (ValueType::SoftAsciiChar, Trigger::Revalidate, 0),
+            (ValueType::SoftAsciiStr, Trigger::Revalidate, 2),
+            (ValueType::SoftAsciiStr, Trigger::Lossy, 1),
+            (ValueType::SoftAsciiString, Trigger::Revalidate, 1),
+            (ValueType::SoftAsciiString, Trigger::Lossy, 3),
+            (ValueType::SoftAsciiString, Trigger::Sanitize, 1)
+        ];
+        assert_eq!(infos.len(), expected.len());
+        for (info, &(value_type, trigger, offset)) in infos.iter().zip(expected.iter()) {
+            assert_eq!(info.value_type(), value_type);
+            assert_eq!(info.trigger(), trigger);
+            assert_eq!(info.offset(), offset);
+            assert_eq!(info.invalid_char(), '↓');
+            assert_eq!(info.detected_at().is_some(), cfg!(debug_assertions));
+            if let Some(location) = info.detected_at() {
+                assert!(location.file().ends_with("violation.rs"));
+            }
+            let tracked = cfg!(any(feature = "track-unchecked", all(feature = "std", debug_assertions)));
+            assert_eq!(info.created_at().is_some(),
+                tracked && value_type != ValueType::SoftAsciiChar && trigger != Trigger::Lossy);
+            if let Some(location) = info.created_at() {
+                assert!(location.file().ends_with("violation.rs"));
+            }
+        }
+        assert!(violation_count(Trigger::Sanitize) > before);
+        assert!(total_violation_count() >= expected.len());
+
+        clear_violation_hook();
+        assert!(violation_hook().is_none());
+    }
+}